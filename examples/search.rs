@@ -21,19 +21,25 @@ fn main() {
                     println!(
                         "{} {} ({}): reporter {} assignee {}",
                         issue.key,
-                        issue.summary().unwrap_or("???".to_owned()),
+                        issue
+                            .summary()
+                            .and_then(|value| value.ok())
+                            .unwrap_or("???".to_owned()),
                         issue
                             .status()
-                            .map(|value| value.name,)
-                            .unwrap_or("???".to_owned(),),
+                            .and_then(|value| value.ok())
+                            .map(|value| value.name)
+                            .unwrap_or("???".to_owned()),
                         issue
                             .reporter()
-                            .map(|value| value.display_name,)
-                            .unwrap_or("???".to_owned(),),
+                            .and_then(|value| value.ok())
+                            .map(|value| value.display_name)
+                            .unwrap_or("???".to_owned()),
                         issue
                             .assignee()
-                            .map(|value| value.display_name,)
-                            .unwrap_or("???".to_owned(),)
+                            .and_then(|value| value.ok())
+                            .map(|value| value.display_name)
+                            .unwrap_or("???".to_owned())
                     );
                 }
             }
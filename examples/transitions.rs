@@ -16,8 +16,10 @@ fn main() {
 
         println!("{:#?}", jira.issues().get(key.clone()));
         let transitions = jira.transitions(key);
-        for option in transitions.list() {
-            println!("{:#?}", option);
+        if let Ok(options) = transitions.list() {
+            for option in options {
+                println!("{:#?}", option);
+            }
         }
         if let Ok(transition_id) = env::var("JIRA_TRANSITION_ID") {
             transitions
@@ -0,0 +1,110 @@
+//! Interfaces for uploading, downloading and managing issue attachments
+
+// Third party
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::Method;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+// Ours
+use super::Jira;
+use crate::attachments::{download_error, multipart, X_ATLASSIAN_TOKEN};
+use crate::{header_value, parse_response, Attachment, EmptyResponse, Result};
+
+/// attachments interface
+#[derive(Debug)]
+pub struct Attachments {
+    jira: Jira,
+}
+
+impl Attachments {
+    pub fn new(jira: &Jira) -> Attachments {
+        Attachments { jira: jira.clone() }
+    }
+
+    /// returns the metadata of an attachment
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/attachment-getAttachment
+    pub async fn get<I>(&self, id: I) -> Result<Attachment>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .get("api", &format!("/attachment/{}", id.into()))
+            .await
+    }
+
+    /// uploads content as an attachment to an issue, returning its metadata.
+    /// content already in memory may be uploaded from a `&[u8]`
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue/{issueIdOrKey}/attachments-addAttachment
+    pub async fn upload<K, F, R>(
+        &self,
+        key: K,
+        filename: F,
+        mut content: R,
+    ) -> Result<Vec<Attachment>>
+    where
+        K: Into<String>,
+        F: Into<String>,
+        R: AsyncRead + Unpin,
+    {
+        let mut bytes = Vec::new();
+        content.read_to_end(&mut bytes).await?;
+        let (content_type, body) = multipart(&filename.into(), &bytes);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, header_value(content_type)?);
+        headers.insert(
+            HeaderName::from_static(X_ATLASSIAN_TOKEN),
+            HeaderValue::from_static("no-check"),
+        );
+        let url = self
+            .jira
+            .url("api", &format!("/issue/{}/attachments", key.into()));
+        let res = self
+            .jira
+            .execute(Method::POST, &url, headers, Some(body))
+            .await?;
+        let (status, headers) = (res.status(), res.headers().clone());
+        let body = res.text().await?;
+        debug!("status {:?} body '{:?}'", status, body);
+        parse_response(status, &headers, &body)
+    }
+
+    /// streams the content of an attachment to a writer, returning the number of bytes written
+    pub async fn download<W>(&self, attachment: &Attachment, out: &mut W) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        debug!("url -> {:?}", attachment.content);
+        let mut res = self
+            .jira
+            .execute(Method::GET, &attachment.content, HeaderMap::new(), None)
+            .await?;
+        let (status, headers) = (res.status(), res.headers().clone());
+        if status.is_success() {
+            let mut written = 0;
+            while let Some(chunk) = res.chunk().await? {
+                out.write_all(&chunk).await?;
+                written += chunk.len() as u64;
+            }
+            out.flush().await?;
+            Ok(written)
+        } else {
+            let body = res.text().await?;
+            Err(download_error(parse_response::<EmptyResponse>(
+                status, &headers, &body,
+            )))
+        }
+    }
+
+    /// deletes an attachment
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/attachment-removeAttachment
+    pub async fn delete<I>(&self, id: I) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .delete::<EmptyResponse>("api", &format!("/attachment/{}", id.into()))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+//! Interfaces for accessing and managing boards
+
+// Third party
+use futures::stream::Stream;
+use url::form_urlencoded;
+
+// Ours
+use super::{next_page, paginate, Jira};
+use crate::{Board, BoardResults, Result, SearchOptions};
+
+#[derive(Debug)]
+pub struct Boards {
+    jira: Jira,
+}
+
+impl Boards {
+    pub fn new(jira: &Jira) -> Boards {
+        Boards { jira: jira.clone() }
+    }
+
+    /// Get a single board
+    ///
+    /// See this [jira docs](https://docs.atlassian.com/jira-software/REST/7.0.4/#agile/1.0/board-getBoard)
+    /// for more information
+    pub async fn get<I>(&self, id: I) -> Result<Board>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .get("agile", &format!("/board/{}", id.into()))
+            .await
+    }
+
+    /// Returns a single page of board results
+    ///
+    /// See the [jira docs](https://docs.atlassian.com/jira-software/REST/latest/#agile/1.0/board-getAllBoards)
+    /// for more information
+    pub async fn list(&self, options: &SearchOptions) -> Result<BoardResults> {
+        let mut path = vec!["/board".to_owned()];
+        let query_options = options.serialize().unwrap_or_default();
+        let query = form_urlencoded::Serializer::new(query_options).finish();
+
+        path.push(query);
+
+        self.jira
+            .get::<BoardResults>("agile", path.join("?").as_ref())
+            .await
+    }
+
+    /// Returns a stream over the boards of consecutive pages of results
+    ///
+    /// See the [jira docs](https://docs.atlassian.com/jira-software/REST/latest/#agile/1.0/board-getAllBoards)
+    /// for more information
+    pub fn stream(&self, options: &SearchOptions) -> impl Stream<Item = Result<Board>> {
+        let jira = self.jira.clone();
+        paginate(options.clone(), move |options| {
+            let jira = jira.clone();
+            async move {
+                let results = jira.boards().list(&options).await?;
+                let next = if !results.is_last && !results.values.is_empty() {
+                    Some(next_page(&options, results.start_at, results.max_results))
+                } else {
+                    None
+                };
+                Ok((results.values, next))
+            }
+        })
+    }
+}
@@ -0,0 +1,110 @@
+//! Interfaces for accessing and managing issue comments
+
+// Third party
+use futures::stream::Stream;
+use url::form_urlencoded;
+
+// Ours
+use super::{next_page, paginate, Jira};
+use crate::comments::{AddComment, CommentResults};
+use crate::{Comment, EmptyResponse, Result, SearchOptions};
+
+/// issue comments interface
+#[derive(Debug)]
+pub struct Comments {
+    jira: Jira,
+    key: String,
+}
+
+impl Comments {
+    pub fn new<K>(jira: &Jira, key: K) -> Comments
+    where
+        K: Into<String>,
+    {
+        Comments {
+            jira: jira.clone(),
+            key: key.into(),
+        }
+    }
+
+    /// returns a single page of comments. use `SearchOptions` to page, to order with
+    /// `order_by("-created")` and to render bodies as html with `expand(vec!["renderedBody"])`
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getComments
+    pub async fn list(&self, options: &SearchOptions) -> Result<CommentResults> {
+        let mut path = vec![format!("/issue/{}/comment", self.key)];
+        let query_options = options.serialize().unwrap_or_default();
+        let query = form_urlencoded::Serializer::new(query_options).finish();
+
+        path.push(query);
+
+        self.jira
+            .get::<CommentResults>("api", path.join("?").as_ref())
+            .await
+    }
+
+    /// returns a stream over comments in consecutive pages of results
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getComments
+    pub fn stream(&self, options: &SearchOptions) -> impl Stream<Item = Result<Comment>> {
+        let jira = self.jira.clone();
+        let key = self.key.clone();
+        paginate(options.clone(), move |options| {
+            let jira = jira.clone();
+            let key = key.clone();
+            async move {
+                let results = jira.comments(key).list(&options).await?;
+                let fetched = results.start_at + results.comments.len() as u64;
+                let next = if !results.comments.is_empty() && fetched < results.total {
+                    Some(next_page(&options, results.start_at, results.max_results))
+                } else {
+                    None
+                };
+                Ok((results.comments, next))
+            }
+        })
+    }
+
+    /// returns a single comment
+    pub async fn get<I>(&self, id: I) -> Result<Comment>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .get("api", &format!("/issue/{}/comment/{}", self.key, id.into()))
+            .await
+    }
+
+    /// adds a comment to the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-addComment
+    pub async fn add(&self, comment: AddComment) -> Result<Comment> {
+        self.jira
+            .post("api", &format!("/issue/{}/comment", self.key), comment)
+            .await
+    }
+
+    /// replaces the body and visibility of a comment
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-updateComment
+    pub async fn update<I>(&self, id: I, comment: AddComment) -> Result<Comment>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .put(
+                "api",
+                &format!("/issue/{}/comment/{}", self.key, id.into()),
+                comment,
+            )
+            .await
+    }
+
+    /// deletes a comment
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-deleteComment
+    pub async fn delete<I>(&self, id: I) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .delete::<EmptyResponse>("api", &format!("/issue/{}/comment/{}", self.key, id.into()))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+//! Interfaces for accessing and managing issues
+
+// Third party
+use futures::stream::Stream;
+use url::form_urlencoded;
+
+// Ours
+use super::{next_page, paginate, Jira};
+use crate::issues::{
+    assignment, bulk_response, delete_path, edit_path, subtasks_error, Account, BulkCreate,
+    BulkCreateResponse, BULK_CREATE_LIMIT,
+};
+use crate::meta::{
+    create_meta_path, fields_path, issue_types_path, CreateMeta, EditMeta, FieldMetaResults,
+    IssueTypeMetaResults,
+};
+use crate::{
+    field_errors, Board, CreateIssue, CreateResponse, EditIssue, EmptyResponse, Issue,
+    IssueResults, Result, SearchOptions,
+};
+
+/// issue options
+#[derive(Debug)]
+pub struct Issues {
+    jira: Jira,
+}
+
+impl Issues {
+    pub fn new(jira: &Jira) -> Issues {
+        Issues { jira: jira.clone() }
+    }
+
+    pub async fn get<I>(&self, id: I) -> Result<Issue>
+    where
+        I: Into<String>,
+    {
+        self.jira.get("api", &format!("/issue/{}", id.into())).await
+    }
+
+    pub async fn create(&self, data: CreateIssue) -> Result<CreateResponse> {
+        self.jira.post("api", "/issue", data).await
+    }
+
+    /// creates many issues, in requests of up to `BULK_CREATE_LIMIT` issues.
+    /// issues which fail to be created are reported with their index
+    /// rather than failing the whole operation. when a request fails after
+    /// earlier requests created issues, the remaining issues are reported as
+    /// failed alongside those created. only a failure of the first request,
+    /// which created nothing, is returned as an error
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-createIssues
+    pub async fn create_bulk(&self, issues: Vec<CreateIssue>) -> Result<BulkCreateResponse> {
+        let mut results = BulkCreateResponse::default();
+        for (n, chunk) in issues.chunks(BULK_CREATE_LIMIT).enumerate() {
+            let offset = n * BULK_CREATE_LIMIT;
+            let response = bulk_response(
+                self.jira
+                    .post(
+                        "api",
+                        "/issue/bulk",
+                        BulkCreate {
+                            issue_updates: chunk,
+                        },
+                    )
+                    .await,
+            );
+            match response {
+                Ok(response) => results.extend(offset, response),
+                Err(e) if n == 0 => return Err(e),
+                Err(e) => {
+                    results.fail(offset, issues.len(), &e);
+                    break;
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    /// edits an existing issue. fields which fail validation are reported
+    /// as an `Error::InvalidFields` whose `errors` are keyed by field id
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-editIssue
+    pub async fn update<I>(&self, id: I, edit: EditIssue) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .put::<EmptyResponse, _>("api", &edit_path(id.into(), &edit), &edit)
+            .await
+            .map_err(field_errors)?;
+        Ok(())
+    }
+
+    /// assigns an issue to a user, or unassigns it when `assignee` is None.
+    /// `Account::automatic` assigns the issue to its project's default assignee
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-assign
+    pub async fn assign<I>(&self, id: I, assignee: Option<Account>) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .put::<EmptyResponse, _>(
+                "api",
+                &format!("/issue/{}/assignee", id.into()),
+                assignment(assignee),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// deletes an issue. deleting an issue with subtasks fails with
+    /// `Error::IssueHasSubtasks` unless `delete_subtasks` is true
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-deleteIssue
+    pub async fn delete<I>(&self, id: I, delete_subtasks: bool) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .delete::<EmptyResponse>("api", &delete_path(id.into(), delete_subtasks))
+            .await
+            .map_err(|e| subtasks_error(e, delete_subtasks))?;
+        Ok(())
+    }
+
+    /// returns the issue types, with their fields, which may be created in
+    /// projects, by key. all projects are listed when no keys are given
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getCreateIssueMeta
+    pub async fn create_meta<P>(&self, project_keys: Vec<P>) -> Result<CreateMeta>
+    where
+        P: Into<String>,
+    {
+        self.jira.get("api", &create_meta_path(project_keys)).await
+    }
+
+    /// returns a page of the issue types which may be created in a project,
+    /// without their fields. available from jira 8.4, which deprecates `create_meta`
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getCreateIssueMetaProjectIssueTypes
+    pub async fn create_meta_issue_types(
+        &self,
+        project_key_or_id: &str,
+        options: &SearchOptions,
+    ) -> Result<IssueTypeMetaResults> {
+        self.jira
+            .get("api", &issue_types_path(project_key_or_id, options))
+            .await
+    }
+
+    /// returns a page of the fields of an issue type which may be created in a project.
+    /// available from jira 8.4
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getCreateIssueMetaFields
+    pub async fn create_meta_fields(
+        &self,
+        project_key_or_id: &str,
+        issuetype_id: &str,
+        options: &SearchOptions,
+    ) -> Result<FieldMetaResults> {
+        self.jira
+            .get(
+                "api",
+                &fields_path(project_key_or_id, issuetype_id, options),
+            )
+            .await
+    }
+
+    /// returns the fields of an issue which may be edited
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getEditIssueMeta
+    pub async fn edit_meta<I>(&self, id: I) -> Result<EditMeta>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .get("api", &format!("/issue/{}/editmeta", id.into()))
+            .await
+    }
+
+    /// returns a single page of issues results
+    /// https://docs.atlassian.com/jira-software/REST/latest/#agile/1.0/board-getIssuesForBoard
+    pub async fn list(&self, board: &Board, options: &SearchOptions) -> Result<IssueResults> {
+        let mut path = vec![format!("/board/{}/issue", board.id)];
+        let query_options = options.serialize().unwrap_or_default();
+        let query = form_urlencoded::Serializer::new(query_options).finish();
+
+        path.push(query);
+
+        self.jira
+            .get::<IssueResults>("agile", path.join("?").as_ref())
+            .await
+    }
+
+    /// returns a stream over the issues of consecutive pages of results
+    /// https://docs.atlassian.com/jira-software/REST/latest/#agile/1.0/board-getIssuesForBoard
+    pub fn stream(
+        &self,
+        board: &Board,
+        options: &SearchOptions,
+    ) -> impl Stream<Item = Result<Issue>> {
+        let jira = self.jira.clone();
+        let board = board.clone();
+        paginate(options.clone(), move |options| {
+            let jira = jira.clone();
+            let board = board.clone();
+            async move {
+                let results = jira.issues().list(&board, &options).await?;
+                let fetched = results.start_at + results.issues.len() as u64;
+                let next = if !results.issues.is_empty() && fetched < results.total {
+                    Some(next_page(&options, results.start_at, results.max_results))
+                } else {
+                    None
+                };
+                Ok((results.issues, next))
+            }
+        })
+    }
+}
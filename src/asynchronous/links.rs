@@ -0,0 +1,60 @@
+//! Interfaces for linking issues
+
+// Ours
+use super::Jira;
+use crate::links::{CreateIssueLink, LinkTypes};
+use crate::{EmptyResponse, IssueLink, LinkType, Result};
+
+/// issue links interface
+#[derive(Debug)]
+pub struct Links {
+    jira: Jira,
+}
+
+impl Links {
+    pub fn new(jira: &Jira) -> Links {
+        Links { jira: jira.clone() }
+    }
+
+    /// links two issues. jira doesn't return the new link, which
+    /// is listed by `Issue#links` on either issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issueLink-linkIssues
+    pub async fn create(&self, link: CreateIssueLink) -> Result<()> {
+        self.jira
+            .post::<EmptyResponse, _>("api", "/issueLink", link)
+            .await?;
+        Ok(())
+    }
+
+    /// returns a link between two issues
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issueLink-getIssueLink
+    pub async fn get<I>(&self, id: I) -> Result<IssueLink>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .get("api", &format!("/issueLink/{}", id.into()))
+            .await
+    }
+
+    /// removes a link between two issues
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issueLink-deleteIssueLink
+    pub async fn delete<I>(&self, id: I) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .delete::<EmptyResponse>("api", &format!("/issueLink/{}", id.into()))
+            .await?;
+        Ok(())
+    }
+
+    /// returns the types of links which may be created
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issueLinkType-getIssueLinkTypes
+    pub async fn types(&self) -> Result<Vec<LinkType>> {
+        self.jira
+            .get::<LinkTypes>("api", "/issueLinkType")
+            .await
+            .map(|types| types.issue_link_types)
+    }
+}
@@ -0,0 +1,420 @@
+//! Asynchronous interfaces for Jira's REST api, built on a non-blocking reqwest client
+//!
+//! These mirror the blocking interfaces at the crate root, with `async` methods
+//! and `Stream`s in place of `Iterator`s for consecutive pages of results.
+//! Enabled with the `async` cargo feature.
+
+// Third party
+use futures::stream::{self, Stream, TryStreamExt};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, COOKIE, RETRY_AFTER};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::future::Future;
+use tokio::time::delay_for;
+
+// Ours
+use crate::session::Session;
+use crate::{parse_response, Credentials, Error, Result, RetryPolicy, SearchOptions};
+
+mod attachments;
+mod boards;
+mod comments;
+mod issues;
+mod links;
+mod remote_links;
+mod search;
+mod sprints;
+mod transitions;
+mod votes;
+mod watchers;
+mod worklogs;
+
+pub use self::attachments::Attachments;
+pub use self::boards::Boards;
+pub use self::comments::Comments;
+pub use self::issues::Issues;
+pub use self::links::Links;
+pub use self::remote_links::RemoteLinks;
+pub use self::search::Search;
+pub use self::sprints::Sprints;
+pub use self::transitions::Transitions;
+pub use self::votes::Votes;
+pub use self::watchers::Watchers;
+pub use self::worklogs::Worklogs;
+
+/// Entrypoint into the asynchronous client interface
+/// https://docs.atlassian.com/jira/REST/latest/
+#[derive(Clone, Debug)]
+pub struct Jira {
+    host: String,
+    credentials: Credentials,
+    client: Client,
+    session: Session,
+    retry: RetryPolicy,
+}
+
+impl Jira {
+    /// creates a new instance of an asynchronous jira client
+    pub fn new<H>(host: H, credentials: Credentials) -> Result<Jira>
+    where
+        H: Into<String>,
+    {
+        Ok(Jira {
+            host: host.into(),
+            client: Client::new(),
+            credentials,
+            session: Session::default(),
+            retry: RetryPolicy::none(),
+        })
+    }
+
+    /// creates a new instance of an asynchronous jira client using a specified reqwest client
+    pub fn from_client<H>(host: H, credentials: Credentials, client: Client) -> Result<Jira>
+    where
+        H: Into<String>,
+    {
+        Ok(Jira {
+            host: host.into(),
+            credentials,
+            client,
+            session: Session::default(),
+            retry: RetryPolicy::none(),
+        })
+    }
+
+    /// sets the policy for retrying requests which fail transiently.
+    /// by default requests are not retried
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Jira {
+        self.retry = retry;
+        self
+    }
+
+    /// return transitions interface
+    pub fn transitions<K>(&self, key: K) -> Transitions
+    where
+        K: Into<String>,
+    {
+        Transitions::new(self, key)
+    }
+
+    /// return attachments interface
+    pub fn attachments(&self) -> Attachments {
+        Attachments::new(self)
+    }
+
+    /// return comments interface for an issue
+    pub fn comments<K>(&self, key: K) -> Comments
+    where
+        K: Into<String>,
+    {
+        Comments::new(self, key)
+    }
+
+    /// return issue links interface
+    pub fn links(&self) -> Links {
+        Links::new(self)
+    }
+
+    /// return remote links interface for an issue
+    pub fn remote_links<K>(&self, key: K) -> RemoteLinks
+    where
+        K: Into<String>,
+    {
+        RemoteLinks::new(self, key)
+    }
+
+    /// return votes interface for an issue
+    pub fn votes<K>(&self, key: K) -> Votes
+    where
+        K: Into<String>,
+    {
+        Votes::new(self, key)
+    }
+
+    /// return watchers interface for an issue
+    pub fn watchers<K>(&self, key: K) -> Watchers
+    where
+        K: Into<String>,
+    {
+        Watchers::new(self, key)
+    }
+
+    /// return worklogs interface
+    pub fn worklogs(&self) -> Worklogs {
+        Worklogs::new(self)
+    }
+
+    /// return search interface
+    pub fn search(&self) -> Search {
+        Search::new(self)
+    }
+
+    // return issues interface
+    pub fn issues(&self) -> Issues {
+        Issues::new(self)
+    }
+
+    // return boards interface
+    pub fn boards(&self) -> Boards {
+        Boards::new(self)
+    }
+
+    // return sprints interface
+    pub fn sprints(&self) -> Sprints {
+        Sprints::new(self)
+    }
+
+    /// ends the current session when using `Credentials::Session`
+    pub async fn logout(&self) -> Result<()> {
+        if let Some(cookie) = self.session.cookie() {
+            let url = Session::url(&self.host);
+            debug!("url -> {:?}", url);
+            self.client
+                .delete(&url)
+                .header(COOKIE, cookie)
+                .send()
+                .await?;
+            self.session.invalidate();
+        }
+        Ok(())
+    }
+
+    /// sends a POST request with a json body to an endpoint of the given api, ie. "api" or "agile"
+    pub async fn post<D, S>(&self, api_name: &str, endpoint: &str, body: S) -> Result<D>
+    where
+        D: DeserializeOwned,
+        S: Serialize,
+    {
+        let data = serde_json::to_string::<S>(&body)?;
+        debug!("Json request: {}", data);
+        self.request::<D>(Method::POST, api_name, endpoint, Some(data.into_bytes()))
+            .await
+    }
+
+    /// sends a PUT request with a json body to an endpoint of the given api
+    pub async fn put<D, S>(&self, api_name: &str, endpoint: &str, body: S) -> Result<D>
+    where
+        D: DeserializeOwned,
+        S: Serialize,
+    {
+        let data = serde_json::to_string::<S>(&body)?;
+        debug!("Json request: {}", data);
+        self.request::<D>(Method::PUT, api_name, endpoint, Some(data.into_bytes()))
+            .await
+    }
+
+    /// sends a PATCH request with a json body to an endpoint of the given api
+    pub async fn patch<D, S>(&self, api_name: &str, endpoint: &str, body: S) -> Result<D>
+    where
+        D: DeserializeOwned,
+        S: Serialize,
+    {
+        let data = serde_json::to_string::<S>(&body)?;
+        debug!("Json request: {}", data);
+        self.request::<D>(Method::PATCH, api_name, endpoint, Some(data.into_bytes()))
+            .await
+    }
+
+    /// sends a GET request to an endpoint of the given api
+    pub async fn get<D>(&self, api_name: &str, endpoint: &str) -> Result<D>
+    where
+        D: DeserializeOwned,
+    {
+        self.request::<D>(Method::GET, api_name, endpoint, None)
+            .await
+    }
+
+    /// sends a DELETE request to an endpoint of the given api
+    ///
+    /// endpoints which respond with 204 No Content may be deserialized into `()` or `EmptyResponse`
+    pub async fn delete<D>(&self, api_name: &str, endpoint: &str) -> Result<D>
+    where
+        D: DeserializeOwned,
+    {
+        self.request::<D>(Method::DELETE, api_name, endpoint, None)
+            .await
+    }
+
+    async fn request<D>(
+        &self,
+        method: Method,
+        api_name: &str,
+        endpoint: &str,
+        body: Option<Vec<u8>>,
+    ) -> Result<D>
+    where
+        D: DeserializeOwned,
+    {
+        let url = self.url(api_name, endpoint);
+        debug!("url -> {:?}", url);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        let res = self.execute(method, &url, headers, body).await?;
+
+        let status = res.status();
+        let headers = res.headers().clone();
+        let body = res.text().await?;
+        debug!("status {:?} body '{:?}'", status, body);
+        parse_response(status, &headers, &body)
+    }
+
+    /// the url of an endpoint of the given api
+    pub(crate) fn url(&self, api_name: &str, endpoint: &str) -> String {
+        format!("{}/rest/{}/latest{}", self.host, api_name, endpoint)
+    }
+
+    /// sends a request with credentials, retrying transient failures and logging in
+    /// again when a session has expired. the response body is left unread
+    pub(crate) async fn execute(
+        &self,
+        method: Method,
+        url: &str,
+        headers: HeaderMap,
+        body: Option<Vec<u8>>,
+    ) -> Result<Response> {
+        let res = self
+            .send(method.clone(), url, headers.clone(), body.clone())
+            .await?;
+        match self.credentials {
+            // the session may have expired, so log in again and retry once
+            Credentials::Session(ref user, ref pass)
+                if res.status() == StatusCode::UNAUTHORIZED =>
+            {
+                self.session.invalidate();
+                self.login(user, pass).await?;
+                self.send(method, url, headers, body).await
+            }
+            _ => Ok(res),
+        }
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        headers: HeaderMap,
+        body: Option<Vec<u8>>,
+    ) -> Result<Response> {
+        let cookie = match self.credentials {
+            Credentials::Session(ref user, ref pass) => match self.session.cookie() {
+                Some(cookie) => Some(cookie),
+                None => Some(self.login(user, pass).await?),
+            },
+            _ => None,
+        };
+        self.retrying(self.retry.retries(&method), || {
+            let mut builder = self
+                .client
+                .request(method.clone(), url)
+                .headers(headers.clone());
+            if let Some(authorization) = self.credentials.authorization(&method, url)? {
+                builder = builder.header(AUTHORIZATION, authorization);
+            }
+            if let Some(ref cookie) = cookie {
+                builder = builder.header(COOKIE, cookie.as_str());
+            }
+            Ok(match body {
+                Some(ref bod) => builder.body(bod.clone()),
+                _ => builder,
+            })
+        })
+        .await
+    }
+
+    /// logs in and stores the issued session cookie
+    async fn login(&self, user: &str, pass: &str) -> Result<String> {
+        let url = Session::url(&self.host);
+        debug!("url -> {:?}", url);
+
+        let credentials = Session::credentials(user, pass)?;
+        let res = self
+            .retrying(true, || {
+                Ok(self
+                    .client
+                    .post(&url)
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(credentials.clone()))
+            })
+            .await?;
+
+        let status = res.status();
+        let headers = res.headers().clone();
+        let body = res.text().await?;
+        debug!("status {:?} body '{:?}'", status, body);
+        self.session.store(status, &headers, &body)
+    }
+
+    /// sends the request built by `request`, building and sending it again while
+    /// the retry policy allows. `retried` is false for requests which are never retried
+    async fn retrying<F>(&self, retried: bool, request: F) -> Result<Response>
+    where
+        F: Fn() -> Result<RequestBuilder>,
+    {
+        let mut attempt = 1;
+        loop {
+            let result = request()?.send().await;
+            let res = match result {
+                Ok(res) => res,
+                Err(e) => match self.retry.delay(attempt, None, None).filter(|_| retried) {
+                    Some(delay) => {
+                        debug!("retrying in {:?} after error {}", delay, e);
+                        delay_for(delay).await;
+                        attempt += 1;
+                        continue;
+                    }
+                    None => return Err(e.into()),
+                },
+            };
+
+            let retry_after = res
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok());
+            if let Some(delay) = self
+                .retry
+                .delay(attempt, Some(res.status()), retry_after)
+                .filter(|_| retried)
+            {
+                debug!("retrying in {:?} after status {:?}", delay, res.status());
+                delay_for(delay).await;
+                attempt += 1;
+                continue;
+            }
+            return Ok(res);
+        }
+    }
+}
+
+/// flattens consecutive pages of results into a stream of items.
+/// `fetch` resolves a page of items along with the options for the next page, if any
+fn paginate<T, F, Fut>(options: SearchOptions, fetch: F) -> impl Stream<Item = Result<T>>
+where
+    F: Fn(SearchOptions) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, Option<SearchOptions>)>>,
+{
+    stream::try_unfold(Some(options), move |next| {
+        let page = next.map(&fetch);
+        async move {
+            match page {
+                Some(page) => {
+                    let (items, next) = page.await?;
+                    let items = items.into_iter().map(Ok::<T, Error>);
+                    Ok::<_, Error>(Some((stream::iter(items), next)))
+                }
+                None => Ok(None),
+            }
+        }
+    })
+    .try_flatten()
+}
+
+/// returns options for the page following one starting at `start_at`
+fn next_page(options: &SearchOptions, start_at: u64, max_results: u64) -> SearchOptions {
+    options
+        .as_builder()
+        .max_results(max_results)
+        .start_at(start_at + max_results)
+        .build()
+}
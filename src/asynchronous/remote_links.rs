@@ -0,0 +1,94 @@
+//! Interfaces for accessing and managing links from issues to remote resources
+
+// Ours
+use super::Jira;
+use crate::remote_links::{global_id_path, CreateRemoteLink, RemoteLink, RemoteLinkCreated};
+use crate::{EmptyResponse, Result};
+
+/// issue remote links interface
+#[derive(Debug)]
+pub struct RemoteLinks {
+    jira: Jira,
+    key: String,
+}
+
+impl RemoteLinks {
+    pub fn new<K>(jira: &Jira, key: K) -> RemoteLinks
+    where
+        K: Into<String>,
+    {
+        RemoteLinks {
+            jira: jira.clone(),
+            key: key.into(),
+        }
+    }
+
+    /// returns all remote links of the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getRemoteIssueLinks
+    pub async fn list(&self) -> Result<Vec<RemoteLink>> {
+        self.jira
+            .get("api", &format!("/issue/{}/remotelink", self.key))
+            .await
+    }
+
+    /// returns a remote link of the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getRemoteIssueLinkById
+    pub async fn get(&self, id: u64) -> Result<RemoteLink> {
+        self.jira
+            .get("api", &format!("/issue/{}/remotelink/{}", self.key, id))
+            .await
+    }
+
+    /// returns the remote link of the issue with a global id
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getRemoteIssueLinks
+    pub async fn find<G>(&self, global_id: G) -> Result<RemoteLink>
+    where
+        G: Into<String>,
+    {
+        self.jira
+            .get("api", &global_id_path(&self.key, &global_id.into()))
+            .await
+    }
+
+    /// creates a remote link, or updates the existing link with the same global id
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-createOrUpdateRemoteIssueLink
+    pub async fn create(&self, link: CreateRemoteLink) -> Result<RemoteLinkCreated> {
+        self.jira
+            .post("api", &format!("/issue/{}/remotelink", self.key), link)
+            .await
+    }
+
+    /// replaces a remote link of the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-updateRemoteIssueLink
+    pub async fn update(&self, id: u64, link: CreateRemoteLink) -> Result<()> {
+        self.jira
+            .put::<EmptyResponse, _>(
+                "api",
+                &format!("/issue/{}/remotelink/{}", self.key, id),
+                link,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// removes a remote link of the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-deleteRemoteIssueLinkById
+    pub async fn delete(&self, id: u64) -> Result<()> {
+        self.jira
+            .delete::<EmptyResponse>("api", &format!("/issue/{}/remotelink/{}", self.key, id))
+            .await?;
+        Ok(())
+    }
+
+    /// removes the remote link of the issue with a global id
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-deleteRemoteIssueLinkByGlobalId
+    pub async fn delete_global<G>(&self, global_id: G) -> Result<()>
+    where
+        G: Into<String>,
+    {
+        self.jira
+            .delete::<EmptyResponse>("api", &global_id_path(&self.key, &global_id.into()))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+//! Interfaces for searching for issues
+
+// Third party
+use futures::stream::Stream;
+use url::form_urlencoded;
+
+// Ours
+use super::{next_page, paginate, Jira};
+use crate::{Issue, Result, SearchOptions, SearchResults};
+
+/// Search interface
+#[derive(Debug)]
+pub struct Search {
+    jira: Jira,
+}
+
+impl Search {
+    pub fn new(jira: &Jira) -> Search {
+        Search { jira: jira.clone() }
+    }
+
+    /// Returns a single page of search results
+    ///
+    /// See the [jira docs](https://docs.atlassian.com/jira/REST/latest/#api/2/search)
+    /// for more information
+    pub async fn list<J>(&self, jql: J, options: &SearchOptions) -> Result<SearchResults>
+    where
+        J: Into<String>,
+    {
+        let mut path = vec!["/search".to_owned()];
+        let query_options = options.serialize().unwrap_or_default();
+        let query = form_urlencoded::Serializer::new(query_options)
+            .append_pair("jql", &jql.into())
+            .finish();
+        path.push(query);
+        self.jira
+            .get::<SearchResults>("api", path.join("?").as_ref())
+            .await
+    }
+
+    /// Return a stream over the issues of consecutive pages of results
+    ///
+    /// See the [jira docs](https://docs.atlassian.com/jira/REST/latest/#api/2/search)
+    /// for more information
+    pub fn stream<J>(&self, jql: J, options: &SearchOptions) -> impl Stream<Item = Result<Issue>>
+    where
+        J: Into<String>,
+    {
+        let jira = self.jira.clone();
+        let jql = jql.into();
+        paginate(options.clone(), move |options| {
+            let jira = jira.clone();
+            let jql = jql.clone();
+            async move {
+                let results = jira.search().list(jql, &options).await?;
+                let fetched = results.start_at + results.issues.len() as u64;
+                let next = if !results.issues.is_empty() && fetched < results.total {
+                    Some(next_page(&options, results.start_at, results.max_results))
+                } else {
+                    None
+                };
+                Ok((results.issues, next))
+            }
+        })
+    }
+}
@@ -0,0 +1,72 @@
+//! Interfaces for accessing and managing sprints
+
+// Third party
+use futures::stream::Stream;
+use url::form_urlencoded;
+
+// Ours
+use super::{next_page, paginate, Jira};
+use crate::{Board, EmptyResponse, Result, SearchOptions, Sprint, SprintResults};
+
+#[derive(Debug)]
+pub struct Sprints {
+    jira: Jira,
+}
+
+#[derive(Serialize, Debug)]
+struct MoveIssues {
+    issues: Vec<String>,
+}
+
+impl Sprints {
+    pub fn new(jira: &Jira) -> Sprints {
+        Sprints { jira: jira.clone() }
+    }
+
+    /// returns a single page of board results
+    /// https://docs.atlassian.com/jira-software/REST/latest/#agile/1.0/board/{boardId}/sprint-getAllSprints
+    pub async fn list(&self, board: &Board, options: &SearchOptions) -> Result<SprintResults> {
+        let mut path = vec![format!("/board/{}/sprint", board.id.to_string())];
+        let query_options = options.serialize().unwrap_or_default();
+        let query = form_urlencoded::Serializer::new(query_options).finish();
+
+        path.push(query);
+
+        self.jira
+            .get::<SprintResults>("agile", path.join("?").as_ref())
+            .await
+    }
+
+    /// move issues into sprint
+    /// https://docs.atlassian.com/jira-software/REST/7.3.1/#agile/1.0/sprint-moveIssuesToSprint
+    pub async fn move_issues(&self, sprint_id: u64, issues: Vec<String>) -> Result<EmptyResponse> {
+        let path = format!("/sprint/{}/issue", sprint_id);
+        let data = MoveIssues { issues };
+
+        self.jira.post("agile", &path, data).await
+    }
+
+    /// returns a stream over the sprints of consecutive pages of results
+    /// https://docs.atlassian.com/jira-software/REST/latest/#agile/1.0/board/{boardId}/sprint-getAllSprints
+    pub fn stream(
+        &self,
+        board: &Board,
+        options: &SearchOptions,
+    ) -> impl Stream<Item = Result<Sprint>> {
+        let jira = self.jira.clone();
+        let board = board.clone();
+        paginate(options.clone(), move |options| {
+            let jira = jira.clone();
+            let board = board.clone();
+            async move {
+                let results = jira.sprints().list(&board, &options).await?;
+                let next = if !results.is_last && !results.values.is_empty() {
+                    Some(next_page(&options, results.start_at, results.max_results))
+                } else {
+                    None
+                };
+                Ok((results.values, next))
+            }
+        })
+    }
+}
@@ -0,0 +1,53 @@
+//! Interfaces for accessing and managing transition
+
+// Ours
+use super::Jira;
+use crate::{
+    field_errors, EmptyResponse, Result, TransitionOption, TransitionOptions,
+    TransitionTriggerOptions,
+};
+
+/// issue transition interface
+#[derive(Debug)]
+pub struct Transitions {
+    jira: Jira,
+    key: String,
+}
+
+impl Transitions {
+    pub fn new<K>(jira: &Jira, key: K) -> Transitions
+    where
+        K: Into<String>,
+    {
+        Transitions {
+            jira: jira.clone(),
+            key: key.into(),
+        }
+    }
+
+    /// return list of transitions options for this issue
+    pub async fn list(&self) -> Result<Vec<TransitionOption>> {
+        self.jira
+            .get::<TransitionOptions>(
+                "api",
+                &format!("/issue/{}/transitions?expand=transitions.fields", self.key),
+            )
+            .await
+            .map(|wrapper| wrapper.transitions)
+    }
+
+    /// trigger a issue transition
+    /// to transition with a resolution use TransitionTrigger::builder(id).resolution(name).
+    /// fields which fail validation are reported as an `Error::InvalidFields`
+    pub async fn trigger(&self, trans: TransitionTriggerOptions) -> Result<()> {
+        self.jira
+            .post::<EmptyResponse, TransitionTriggerOptions>(
+                "api",
+                &format!("/issue/{}/transitions", self.key),
+                trans,
+            )
+            .await
+            .map_err(field_errors)?;
+        Ok(())
+    }
+}
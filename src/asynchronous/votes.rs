@@ -0,0 +1,60 @@
+//! Interfaces for accessing and managing issue votes
+
+// Third party
+use reqwest::Method;
+
+// Ours
+use super::Jira;
+use crate::votes::VoteResults;
+use crate::{EmptyResponse, Result};
+
+/// issue votes interface
+#[derive(Debug)]
+pub struct Votes {
+    jira: Jira,
+    key: String,
+}
+
+impl Votes {
+    pub fn new<K>(jira: &Jira, key: K) -> Votes
+    where
+        K: Into<String>,
+    {
+        Votes {
+            jira: jira.clone(),
+            key: key.into(),
+        }
+    }
+
+    /// returns the votes for the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getVotes
+    pub async fn list(&self) -> Result<VoteResults> {
+        self.jira
+            .get("api", &format!("/issue/{}/votes", self.key))
+            .await
+    }
+
+    /// votes for the issue as the current user
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-addVote
+    pub async fn vote(&self) -> Result<()> {
+        // jira expects no body at all, rather than a json null
+        self.jira
+            .request::<EmptyResponse>(
+                Method::POST,
+                "api",
+                &format!("/issue/{}/votes", self.key),
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// removes the current user's vote for the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-removeVote
+    pub async fn unvote(&self) -> Result<()> {
+        self.jira
+            .delete::<EmptyResponse>("api", &format!("/issue/{}/votes", self.key))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+//! Interfaces for accessing and managing issue watchers
+
+// Ours
+use super::Jira;
+use crate::watchers::{remove_path, WatcherResults};
+use crate::{Account, EmptyResponse, Result};
+
+/// issue watchers interface
+#[derive(Debug)]
+pub struct Watchers {
+    jira: Jira,
+    key: String,
+}
+
+impl Watchers {
+    pub fn new<K>(jira: &Jira, key: K) -> Watchers
+    where
+        K: Into<String>,
+    {
+        Watchers {
+            jira: jira.clone(),
+            key: key.into(),
+        }
+    }
+
+    /// returns the users watching the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getIssueWatchers
+    pub async fn list(&self) -> Result<WatcherResults> {
+        self.jira
+            .get("api", &format!("/issue/{}/watchers", self.key))
+            .await
+    }
+
+    /// adds a user, by name or `Account`, as a watcher of the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-addWatcher
+    pub async fn add<U>(&self, user: U) -> Result<()>
+    where
+        U: Into<Account>,
+    {
+        self.jira
+            .post::<EmptyResponse, _>(
+                "api",
+                &format!("/issue/{}/watchers", self.key),
+                // jira expects the name, or account id, as a json string
+                String::from(user.into().param().1),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// removes a user, by name or `Account`, as a watcher of the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-removeWatcher
+    pub async fn remove<U>(&self, user: U) -> Result<()>
+    where
+        U: Into<Account>,
+    {
+        self.jira
+            .delete::<EmptyResponse>("api", &remove_path(&self.key, &user.into()))
+            .await?;
+        Ok(())
+    }
+}
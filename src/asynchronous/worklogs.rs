@@ -0,0 +1,136 @@
+//! Interfaces for accessing and managing issue worklogs
+
+// Ours
+use super::Jira;
+use crate::worklogs::{
+    add_path, worklog_path, worklogs_path, AddWorklog, AdjustEstimate, UpdateEstimate, Worklog,
+    WorklogChanges, WorklogIds, WorklogResults, WORKLOG_LIST_LIMIT,
+};
+use crate::{EmptyResponse, Result, SearchOptions};
+
+/// worklogs interface
+#[derive(Debug)]
+pub struct Worklogs {
+    jira: Jira,
+}
+
+impl Worklogs {
+    pub fn new(jira: &Jira) -> Worklogs {
+        Worklogs { jira: jira.clone() }
+    }
+
+    /// returns a single page of the worklogs of an issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getIssueWorklog
+    pub async fn list<K>(&self, key: K, options: &SearchOptions) -> Result<WorklogResults>
+    where
+        K: Into<String>,
+    {
+        self.jira
+            .get("api", &worklogs_path(&key.into(), options))
+            .await
+    }
+
+    /// returns a single worklog of an issue
+    pub async fn get<K, I>(&self, key: K, id: I) -> Result<Worklog>
+    where
+        K: Into<String>,
+        I: Into<String>,
+    {
+        self.jira
+            .get(
+                "api",
+                &format!("/issue/{}/worklog/{}", key.into(), id.into()),
+            )
+            .await
+    }
+
+    /// logs time against an issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-addWorklog
+    pub async fn add<K>(
+        &self,
+        key: K,
+        worklog: AddWorklog,
+        adjust: AdjustEstimate,
+    ) -> Result<Worklog>
+    where
+        K: Into<String>,
+    {
+        self.jira
+            .post("api", &add_path(&key.into(), &adjust), worklog)
+            .await
+    }
+
+    /// replaces a worklog of an issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-updateWorklog
+    pub async fn update<K, I>(
+        &self,
+        key: K,
+        id: I,
+        worklog: AddWorklog,
+        adjust: UpdateEstimate,
+    ) -> Result<Worklog>
+    where
+        K: Into<String>,
+        I: Into<String>,
+    {
+        self.jira
+            .put(
+                "api",
+                &worklog_path(&key.into(), &id.into(), &adjust.into()),
+                worklog,
+            )
+            .await
+    }
+
+    /// deletes a worklog of an issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-deleteWorklog
+    pub async fn delete<K, I>(&self, key: K, id: I, adjust: AdjustEstimate) -> Result<()>
+    where
+        K: Into<String>,
+        I: Into<String>,
+    {
+        self.jira
+            .delete::<EmptyResponse>("api", &worklog_path(&key.into(), &id.into(), &adjust))
+            .await?;
+        Ok(())
+    }
+
+    /// returns a single page of the ids of worklogs updated since a time,
+    /// in milliseconds since the epoch
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/worklog-getIdsOfWorklogsModifiedSince
+    pub async fn updated(&self, since: u64) -> Result<WorklogChanges> {
+        self.jira
+            .get("api", &format!("/worklog/updated?since={}", since))
+            .await
+    }
+
+    /// returns worklogs by id, across issues
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/worklog-getWorklogsForIds
+    pub async fn list_by_ids(&self, ids: &[u64]) -> Result<Vec<Worklog>> {
+        let mut worklogs = vec![];
+        for chunk in ids.chunks(WORKLOG_LIST_LIMIT) {
+            worklogs.extend(
+                self.jira
+                    .post::<Vec<Worklog>, _>("api", "/worklog/list", WorklogIds { ids: chunk })
+                    .await?,
+            );
+        }
+        Ok(worklogs)
+    }
+
+    /// returns all worklogs updated since a time, in milliseconds since the epoch,
+    /// following every page of `updated` changes
+    pub async fn updated_since(&self, since: u64) -> Result<Vec<Worklog>> {
+        let mut ids = vec![];
+        let mut since = since;
+        loop {
+            let changes = self.updated(since).await?;
+            ids.extend(changes.values.iter().map(|change| change.worklog_id));
+            if changes.last_page || changes.until <= since {
+                break;
+            }
+            since = changes.until;
+        }
+        self.list_by_ids(&ids).await
+    }
+}
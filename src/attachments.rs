@@ -0,0 +1,124 @@
+//! Interfaces for uploading, downloading and managing issue attachments
+
+// Third party
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::Method;
+use std::collections::hash_map::RandomState;
+use std::fs::File;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+// Ours
+use crate::{header_value, read_response, Attachment, EmptyResponse, Error, Jira, Result};
+
+/// jira rejects uploads without this header as a cross site request forgery
+pub(crate) const X_ATLASSIAN_TOKEN: &str = "x-atlassian-token";
+
+/// attachments interface
+#[derive(Debug)]
+pub struct Attachments {
+    jira: Jira,
+}
+
+impl Attachments {
+    pub fn new(jira: &Jira) -> Attachments {
+        Attachments { jira: jira.clone() }
+    }
+
+    /// returns the metadata of an attachment
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/attachment-getAttachment
+    pub fn get<I>(&self, id: I) -> Result<Attachment>
+    where
+        I: Into<String>,
+    {
+        self.jira.get("api", &format!("/attachment/{}", id.into()))
+    }
+
+    /// uploads content as an attachment to an issue, returning its metadata
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue/{issueIdOrKey}/attachments-addAttachment
+    pub fn upload<K, F, R>(&self, key: K, filename: F, mut content: R) -> Result<Vec<Attachment>>
+    where
+        K: Into<String>,
+        F: Into<String>,
+        R: Read,
+    {
+        let mut bytes = Vec::new();
+        content.read_to_end(&mut bytes)?;
+        let (content_type, body) = multipart(&filename.into(), &bytes);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, header_value(content_type)?);
+        headers.insert(
+            HeaderName::from_static(X_ATLASSIAN_TOKEN),
+            HeaderValue::from_static("no-check"),
+        );
+        let url = self
+            .jira
+            .url("api", &format!("/issue/{}/attachments", key.into()));
+        read_response(self.jira.execute(Method::POST, &url, headers, Some(body))?)
+    }
+
+    /// uploads a file as an attachment to an issue, named after the file
+    pub fn upload_file<K, P>(&self, key: K, path: P) -> Result<Vec<Attachment>>
+    where
+        K: Into<String>,
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.upload(key, filename, File::open(path)?)
+    }
+
+    /// streams the content of an attachment to a writer, returning the number of bytes written
+    pub fn download<W>(&self, attachment: &Attachment, out: &mut W) -> Result<u64>
+    where
+        W: Write,
+    {
+        debug!("url -> {:?}", attachment.content);
+        let mut res =
+            self.jira
+                .execute(Method::GET, &attachment.content, HeaderMap::new(), None)?;
+        if res.status.is_success() {
+            Ok(io::copy(&mut res.body, out)?)
+        } else {
+            Err(download_error(read_response::<EmptyResponse>(res)))
+        }
+    }
+
+    /// deletes an attachment
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/attachment-removeAttachment
+    pub fn delete<I>(&self, id: I) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .delete::<EmptyResponse>("api", &format!("/attachment/{}", id.into()))?;
+        Ok(())
+    }
+}
+
+/// encodes a file as a multipart/form-data body, returning its content type and body
+pub(crate) fn multipart(filename: &str, content: &[u8]) -> (String, Vec<u8>) {
+    let boundary = format!("goji-{:016x}", RandomState::new().build_hasher().finish());
+    let mut body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+        boundary,
+        filename.replace('"', "%22").replace(['\r', '\n'], "")
+    )
+    .into_bytes();
+    body.extend_from_slice(content);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+    (format!("multipart/form-data; boundary={}", boundary), body)
+}
+
+/// the error for a download which did not succeed
+pub(crate) fn download_error(result: Result<EmptyResponse>) -> Error {
+    match result {
+        Err(e) => e,
+        Ok(_) => Error::IO(io::Error::other("attachment content was not returned")),
+    }
+}
@@ -110,6 +110,12 @@ impl SearchOptionsBuilder {
         self
     }
 
+    /// orders results by a field, ie. "created" or "-created" for descending
+    pub fn order_by(&mut self, field: &str) -> &mut SearchOptionsBuilder {
+        self.params.insert("orderBy", field.to_string());
+        self
+    }
+
     pub fn state(&mut self, s: &str) -> &mut SearchOptionsBuilder {
         self.params.insert("state", s.to_string());
         self
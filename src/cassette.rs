@@ -0,0 +1,323 @@
+//! Record and replay transports for deterministic tests
+//!
+//! A `Recorder` wraps another transport, capturing each request and response
+//! to a json cassette file. A `Replayer` serves a cassette's responses back
+//! without a network, so test suites can run offline.
+//!
+//! ```no_run
+//! use goji::cassette::{Recorder, Replayer};
+//! use goji::{Credentials, Jira, ReqwestTransport};
+//!
+//! // record against a live jira once
+//! let recorder = Recorder::new(ReqwestTransport::default(), "tests/cassettes/issue.json")
+//!     .scrub("my-password");
+//! let jira = Jira::from_transport(
+//!     "https://jira.example.com",
+//!     Credentials::Basic("me".into(), "my-password".into()),
+//!     recorder,
+//! )
+//! .unwrap();
+//! jira.issues().get("TEST-1").unwrap();
+//!
+//! // then replay offline
+//! let replayer = Replayer::from_file("tests/cassettes/issue.json").unwrap();
+//! let jira = Jira::from_transport(
+//!     "https://jira.example.com",
+//!     Credentials::Basic("me".into(), "my-password".into()),
+//!     replayer,
+//! )
+//! .unwrap();
+//! jira.issues().get("TEST-1").unwrap();
+//! ```
+
+// Third party
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use url::Url;
+
+// Ours
+use crate::{Error, HttpRequest, HttpResponse, Result, Transport};
+
+/// replaces scrubbed credentials in recorded interactions
+const SCRUBBED: &str = "[scrubbed]";
+
+/// headers which carry credentials and are never recorded
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+];
+
+/// the path of jira's session resource, whose login requests carry a password
+/// and whose responses carry the session cookie
+const SESSION_PATH: &str = "/rest/auth/1/session";
+
+/// json properties of session logins which carry credentials and are never recorded
+const SENSITIVE_PROPERTIES: &[&str] = &["/password", "/session/value"];
+
+/// a recorded request and its response
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: BTreeMap<String, String>,
+    pub body: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub headers: BTreeMap<String, String>,
+    /// the body, when it is valid utf8
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// the base64 encoded body, when it is binary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_base64: Option<String>,
+}
+
+/// a series of recorded interactions, as stored in a cassette file
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// reads a cassette from a json file
+    pub fn load<P>(path: P) -> Result<Cassette>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// writes a cassette to a json file
+    pub fn save<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+}
+
+/// a transport which records interactions with another transport to a cassette file
+///
+/// The cassette is rewritten after every interaction. Credential headers,
+/// session login passwords and issued session cookies are never recorded, and
+/// secrets registered with `scrub` are replaced wherever they appear
+#[derive(Debug)]
+pub struct Recorder<T> {
+    inner: T,
+    path: PathBuf,
+    secrets: Vec<String>,
+    cassette: Mutex<Cassette>,
+}
+
+impl<T> Recorder<T>
+where
+    T: Transport,
+{
+    /// creates a new recorder writing to a cassette file at `path`
+    pub fn new<P>(inner: T, path: P) -> Recorder<T>
+    where
+        P: Into<PathBuf>,
+    {
+        Recorder {
+            inner,
+            path: path.into(),
+            secrets: vec![],
+            cassette: Mutex::new(Cassette::default()),
+        }
+    }
+
+    /// replaces a secret with a placeholder wherever it appears in recorded interactions
+    pub fn scrub<S>(mut self, secret: S) -> Recorder<T>
+    where
+        S: Into<String>,
+    {
+        let secret = secret.into();
+        if !secret.is_empty() {
+            self.secrets.push(secret);
+        }
+        self
+    }
+
+    fn scrubbed(&self, value: &str) -> String {
+        self.secrets.iter().fold(value.to_owned(), |value, secret| {
+            value.replace(secret.as_str(), SCRUBBED)
+        })
+    }
+
+    fn record_headers(&self, headers: &HeaderMap) -> BTreeMap<String, String> {
+        headers
+            .iter()
+            .filter(|(name, _)| !SENSITIVE_HEADERS.contains(&name.as_str()))
+            .map(|(name, value)| {
+                (
+                    name.as_str().to_owned(),
+                    self.scrubbed(&String::from_utf8_lossy(value.as_bytes())),
+                )
+            })
+            .collect()
+    }
+
+    fn record_body(&self, url: &str, text: &str) -> String {
+        if !is_session(url) {
+            return self.scrubbed(text);
+        }
+        let redacted = match serde_json::from_str::<Value>(text) {
+            Ok(mut value) => {
+                for property in SENSITIVE_PROPERTIES {
+                    if let Some(sensitive) = value.pointer_mut(property) {
+                        *sensitive = Value::String(SCRUBBED.to_owned());
+                    }
+                }
+                value.to_string()
+            }
+            // bodies which can't be inspected may carry credentials
+            Err(_) if text.is_empty() => String::new(),
+            Err(_) => SCRUBBED.to_owned(),
+        };
+        self.scrubbed(&redacted)
+    }
+}
+
+/// whether a url addresses jira's session resource
+fn is_session(url: &str) -> bool {
+    Url::parse(url)
+        .map(|url| url.path().trim_end_matches('/').ends_with(SESSION_PATH))
+        .unwrap_or(false)
+}
+
+impl<T> Transport for Recorder<T>
+where
+    T: Transport,
+{
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let url = request.url.clone();
+        let recorded_request = RecordedRequest {
+            method: request.method.to_string(),
+            url: self.scrubbed(&request.url),
+            headers: self.record_headers(&request.headers),
+            body: request
+                .body
+                .as_ref()
+                .map(|body| self.record_body(&url, &String::from_utf8_lossy(body))),
+        };
+
+        let mut response = self.inner.send(request)?;
+        let mut body = Vec::new();
+        response.body.read_to_end(&mut body)?;
+
+        let (text, binary) = match String::from_utf8(body.clone()) {
+            Ok(text) => (Some(self.record_body(&url, &text)), None),
+            Err(_) => (None, Some(base64::encode(&body))),
+        };
+        let interaction = Interaction {
+            request: recorded_request,
+            response: RecordedResponse {
+                status: response.status.as_u16(),
+                headers: self.record_headers(&response.headers),
+                body: text,
+                body_base64: binary,
+            },
+        };
+
+        let mut cassette = self.cassette.lock().unwrap();
+        cassette.interactions.push(interaction);
+        cassette.save(&self.path)?;
+
+        Ok(HttpResponse {
+            status: response.status,
+            headers: response.headers,
+            body: Box::new(Cursor::new(body)),
+        })
+    }
+}
+
+/// a transport which serves responses from a cassette without a network
+///
+/// Requests are matched to unplayed interactions by method and url,
+/// in the order they were recorded
+#[derive(Debug)]
+pub struct Replayer {
+    interactions: Mutex<Vec<Option<Interaction>>>,
+}
+
+impl Replayer {
+    /// creates a new replayer for a cassette
+    pub fn new(cassette: Cassette) -> Replayer {
+        Replayer {
+            interactions: Mutex::new(cassette.interactions.into_iter().map(Some).collect()),
+        }
+    }
+
+    /// creates a new replayer for a cassette file
+    pub fn from_file<P>(path: P) -> Result<Replayer>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(Replayer::new(Cassette::load(path)?))
+    }
+}
+
+impl Transport for Replayer {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let method = request.method.to_string();
+        let interaction = self
+            .interactions
+            .lock()
+            .unwrap()
+            .iter_mut()
+            .find(|interaction| match interaction {
+                Some(interaction) => {
+                    interaction.request.method == method && interaction.request.url == request.url
+                }
+                None => false,
+            })
+            .and_then(|interaction| interaction.take())
+            .ok_or_else(|| {
+                Error::IO(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("no recorded interaction for {} {}", method, request.url),
+                ))
+            })?;
+
+        let response = interaction.response;
+        let mut headers = HeaderMap::new();
+        for (name, value) in response.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(&value),
+            ) {
+                headers.insert(name, value);
+            }
+        }
+        let body = match (response.body, response.body_base64) {
+            (Some(text), _) => text.into_bytes(),
+            (None, Some(binary)) => base64::decode(&binary)
+                .map_err(|e| Error::IO(io::Error::new(io::ErrorKind::InvalidData, e)))?,
+            (None, None) => vec![],
+        };
+
+        Ok(HttpResponse {
+            status: StatusCode::from_u16(response.status)
+                .map_err(|e| Error::IO(io::Error::new(io::ErrorKind::InvalidData, e)))?,
+            headers,
+            body: Box::new(Cursor::new(body)),
+        })
+    }
+}
@@ -0,0 +1,179 @@
+//! Interfaces for accessing and managing issue comments
+
+// Third party
+use url::form_urlencoded;
+
+// Ours
+use crate::{Comment, EmptyResponse, Jira, Result, SearchOptions, Visibility};
+
+/// issue comments interface
+#[derive(Debug)]
+pub struct Comments {
+    jira: Jira,
+    key: String,
+}
+
+/// the body of a new or edited comment
+#[derive(Serialize, Debug, Clone)]
+pub struct AddComment {
+    pub body: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+}
+
+impl AddComment {
+    /// creates a new comment visible to anyone who can see the issue
+    pub fn new<B>(body: B) -> AddComment
+    where
+        B: Into<String>,
+    {
+        AddComment {
+            body: body.into(),
+            visibility: None,
+        }
+    }
+
+    /// restricts who may see the comment
+    pub fn visibility(mut self, visibility: Visibility) -> AddComment {
+        self.visibility = Some(visibility);
+        self
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CommentResults {
+    #[serde(rename = "startAt")]
+    pub start_at: u64,
+    #[serde(rename = "maxResults")]
+    pub max_results: u64,
+    pub total: u64,
+    pub comments: Vec<Comment>,
+}
+
+impl Comments {
+    pub fn new<K>(jira: &Jira, key: K) -> Comments
+    where
+        K: Into<String>,
+    {
+        Comments {
+            jira: jira.clone(),
+            key: key.into(),
+        }
+    }
+
+    /// returns a single page of comments. use `SearchOptions` to page, to order with
+    /// `order_by("-created")` and to render bodies as html with `expand(vec!["renderedBody"])`
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getComments
+    pub fn list(&self, options: &SearchOptions) -> Result<CommentResults> {
+        let mut path = vec![format!("/issue/{}/comment", self.key)];
+        let query_options = options.serialize().unwrap_or_default();
+        let query = form_urlencoded::Serializer::new(query_options).finish();
+
+        path.push(query);
+
+        self.jira
+            .get::<CommentResults>("api", path.join("?").as_ref())
+    }
+
+    /// returns a type which may be used to iterate over comments in consecutive pages of results
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getComments
+    pub fn iter<'a>(&self, options: &'a SearchOptions) -> Result<CommentsIter<'a>> {
+        CommentsIter::new(self.key.clone(), options, &self.jira)
+    }
+
+    /// returns a single comment
+    pub fn get<I>(&self, id: I) -> Result<Comment>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .get("api", &format!("/issue/{}/comment/{}", self.key, id.into()))
+    }
+
+    /// adds a comment to the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-addComment
+    pub fn add(&self, comment: AddComment) -> Result<Comment> {
+        self.jira
+            .post("api", &format!("/issue/{}/comment", self.key), comment)
+    }
+
+    /// replaces the body and visibility of a comment
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-updateComment
+    pub fn update<I>(&self, id: I, comment: AddComment) -> Result<Comment>
+    where
+        I: Into<String>,
+    {
+        self.jira.put(
+            "api",
+            &format!("/issue/{}/comment/{}", self.key, id.into()),
+            comment,
+        )
+    }
+
+    /// deletes a comment
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-deleteComment
+    pub fn delete<I>(&self, id: I) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        self.jira.delete::<EmptyResponse>(
+            "api",
+            &format!("/issue/{}/comment/{}", self.key, id.into()),
+        )?;
+        Ok(())
+    }
+}
+
+/// provides an iterator over comments in multiple pages of results
+#[derive(Debug)]
+pub struct CommentsIter<'a> {
+    jira: Jira,
+    key: String,
+    results: CommentResults,
+    search_options: &'a SearchOptions,
+}
+
+impl<'a> CommentsIter<'a> {
+    fn new(key: String, options: &'a SearchOptions, jira: &Jira) -> Result<Self> {
+        let mut results = jira.comments(key.as_str()).list(options)?;
+        // comments are popped from the end of a page, so keep them in reverse
+        results.comments.reverse();
+        Ok(CommentsIter {
+            jira: jira.clone(),
+            key,
+            results,
+            search_options: options,
+        })
+    }
+
+    fn more(&self) -> bool {
+        (self.results.start_at + self.results.max_results) < self.results.total
+    }
+}
+
+impl<'a> Iterator for CommentsIter<'a> {
+    type Item = Comment;
+    fn next(&mut self) -> Option<Comment> {
+        self.results.comments.pop().or_else(|| {
+            if self.more() {
+                match self.jira.comments(self.key.as_str()).list(
+                    &self
+                        .search_options
+                        .as_builder()
+                        .max_results(self.results.max_results)
+                        .start_at(self.results.start_at + self.results.max_results)
+                        .build(),
+                ) {
+                    Ok(mut new_results) => {
+                        new_results.comments.reverse();
+                        self.results = new_results;
+                        self.results.comments.pop()
+                    }
+                    _ => None,
+                }
+            } else {
+                None
+            }
+        })
+    }
+}
@@ -1,4 +1,5 @@
 // Third party
+use reqwest::header::HeaderMap;
 use reqwest::Error as HttpError;
 use reqwest::StatusCode;
 use serde_json::error::Error as SerdeError;
@@ -9,7 +10,10 @@ use crate::Errors;
 
 /// an enumeration over potential errors
 /// that may happen when sending a request to jira
+///
+/// Further errors may be enabled by cargo features, ie. `OAuth` with the `oauth` feature
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// error associated with http request
     Http(HttpError),
@@ -19,12 +23,50 @@ pub enum Error {
     Serde(SerdeError),
     /// client request errors
     Fault { code: StatusCode, errors: Errors },
+    /// server errors, with the raw response which is often an html error page
+    Server {
+        code: StatusCode,
+        headers: HeaderMap,
+        body: String,
+    },
     /// invalid credentials
     Unauthorized,
+    /// a session login was refused, ie. when a captcha must be answered or the account is locked.
+    /// `reason` holds jira's `X-Authentication-Denied-Reason` header, if any
+    AuthenticationDenied {
+        reason: Option<String>,
+        errors: Errors,
+    },
+    /// an issue could not be deleted because it has subtasks
+    IssueHasSubtasks(Errors),
+    /// fields of an issue which is being edited or transitioned failed validation.
+    /// `errors` holds the messages for each field, by field id
+    InvalidFields(Errors),
     /// HTTP method is not allowed
     MethodNotAllowed,
     /// Page not found
     NotFound,
+    /// error signing a request or obtaining an oauth token
+    #[cfg(feature = "oauth")]
+    OAuth(String),
+}
+
+#[cfg(feature = "oauth")]
+impl From<::openssl::error::ErrorStack> for Error {
+    fn from(error: ::openssl::error::ErrorStack) -> Error {
+        Error::OAuth(error.to_string())
+    }
+}
+
+/// distinguishes bad requests rejecting the fields of an issue from other faults
+pub(crate) fn field_errors(error: Error) -> Error {
+    match error {
+        Error::Fault {
+            code: StatusCode::BAD_REQUEST,
+            errors,
+        } if !errors.errors.is_empty() => Error::InvalidFields(errors),
+        error => error,
+    }
 }
 
 impl From<SerdeError> for Error {
@@ -57,34 +99,37 @@ impl ::std::fmt::Display for Error {
                 ref code,
                 ref errors,
             } => writeln!(f, "Jira Client Error ({}):\n{:#?}", code, errors),
-            _ => writeln!(f, "Could not connect to Jira: {}!", self),
+            Server {
+                ref code, ref body, ..
+            } => {
+                writeln!(f, "Jira Server Error ({}):\n{}", code, body)
+            }
+            IssueHasSubtasks(ref errors) => {
+                writeln!(f, "Issue has subtasks and was not deleted:\n{:#?}", errors)
+            }
+            InvalidFields(ref errors) => writeln!(f, "Issue fields are invalid:\n{:#?}", errors),
+            Unauthorized => writeln!(f, "Could not connect to Jira: Unauthorized!"),
+            AuthenticationDenied { ref reason, .. } => writeln!(
+                f,
+                "Could not connect to Jira: Authentication denied ({})",
+                reason.as_deref().unwrap_or("no reason given")
+            ),
+            MethodNotAllowed => writeln!(f, "Could not connect to Jira: MethodNotAllowed!"),
+            NotFound => writeln!(f, "Could not connect to Jira: NotFound!"),
+            #[cfg(feature = "oauth")]
+            OAuth(ref e) => writeln!(f, "OAuth Error: {}", e),
         }
     }
 }
 
 impl ::std::error::Error for Error {
-    fn description(&self) -> &str {
-        use crate::Error::*;
-
-        match *self {
-            Http(ref e) => e.description(),
-            IO(ref e) => e.description(),
-            Serde(ref e) => e.description(),
-            Fault { .. } => "Jira client error",
-            Unauthorized => "Unauthorized",
-            MethodNotAllowed => "MethodNotAllowed",
-            NotFound => "NotFound",
-        }
-    }
-
-    fn cause(&self) -> Option<&dyn (::std::error::Error)> {
+    fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
         use crate::Error::*;
 
         match *self {
             Http(ref e) => Some(e),
             IO(ref e) => Some(e),
             Serde(ref e) => Some(e),
-            Fault { .. } => None,
             _ => None,
         }
     }
@@ -1,10 +1,20 @@
 //! Interfaces for accessing and managing issues
 
 // Third party
+use reqwest::StatusCode;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
 use url::form_urlencoded;
 
 // Ours
-use crate::{Board, Issue, Jira, Result, SearchOptions};
+use crate::meta::{
+    create_meta_path, fields_path, issue_types_path, CreateMeta, EditMeta, FieldMetaResults,
+    IssueTypeMetaResults,
+};
+use crate::{
+    field_errors, Board, EmptyResponse, Error, Errors, Issue, Jira, Result, SearchOptions,
+};
 
 /// issue options
 #[derive(Debug)]
@@ -12,9 +22,54 @@ pub struct Issues {
     jira: Jira,
 }
 
-#[derive(Serialize, Debug, Clone)]
-pub struct Assignee {
-    pub name: String,
+/// identifies a user in requests, ie. an issue's assignee or a watcher.
+/// Jira Server identifies users by name, and Jira Cloud by account id
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum Account {
+    Name(String),
+    AccountId(String),
+}
+
+impl Account {
+    /// the request parameter and value identifying the user
+    pub(crate) fn param(&self) -> (&'static str, &str) {
+        match *self {
+            Account::Name(ref name) => ("username", name),
+            Account::AccountId(ref id) => ("accountId", id),
+        }
+    }
+
+    /// assigns an issue to its project's default assignee on Jira Server
+    pub fn automatic() -> Account {
+        Account::Name("-1".into())
+    }
+
+    /// assigns an issue to its project's default assignee on Jira Cloud
+    pub fn automatic_account() -> Account {
+        Account::AccountId("-1".into())
+    }
+}
+
+impl From<String> for Account {
+    fn from(name: String) -> Account {
+        Account::Name(name)
+    }
+}
+
+impl<'a> From<&'a str> for Account {
+    fn from(name: &'a str) -> Account {
+        Account::Name(name.into())
+    }
+}
+
+/// the body of an assignment. jira server unassigns issues without a name,
+/// and jira cloud without an account id
+pub(crate) fn assignment(assignee: Option<Account>) -> Value {
+    match assignee {
+        Some(assignee) => serde_json::to_value(assignee).expect("Value to serialize"),
+        None => json!({ "name": null, "accountId": null }),
+    }
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -37,25 +92,165 @@ pub struct Component {
     pub name: String,
 }
 
-#[derive(Serialize, Debug)]
+/// fields of a new issue. only `project`, `issuetype` and `summary` are required
+#[derive(Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Fields {
-    pub assignee: Assignee,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<Account>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub components: Vec<Component>,
-    pub description: String,
-    pub environment: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
     pub issuetype: IssueType,
-    pub priority: Priority,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
     pub project: Project,
-    pub reporter: Assignee,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reporter: Option<Account>,
     pub summary: String,
+    /// any other fields, ie. `customfield_10010`, by field id
+    #[serde(flatten)]
+    pub custom: BTreeMap<String, Value>,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone)]
 pub struct CreateIssue {
     pub fields: Fields,
 }
 
+impl CreateIssue {
+    /// return a new instance of a builder for an issue in a project, of an
+    /// issue type identified by id
+    pub fn builder<P, T, S>(project_key: P, issuetype_id: T, summary: S) -> CreateIssueBuilder
+    where
+        P: Into<String>,
+        T: Into<String>,
+        S: Into<String>,
+    {
+        CreateIssueBuilder::new(project_key, issuetype_id, summary)
+    }
+}
+
+/// a builder interface for new issues
+/// Typically this is initialized with CreateIssue::builder(project_key, issuetype_id, summary)
+#[derive(Debug)]
+pub struct CreateIssueBuilder {
+    fields: Fields,
+}
+
+impl CreateIssueBuilder {
+    pub fn new<P, T, S>(project_key: P, issuetype_id: T, summary: S) -> CreateIssueBuilder
+    where
+        P: Into<String>,
+        T: Into<String>,
+        S: Into<String>,
+    {
+        CreateIssueBuilder {
+            fields: Fields {
+                assignee: None,
+                components: vec![],
+                description: None,
+                environment: None,
+                issuetype: IssueType {
+                    id: issuetype_id.into(),
+                },
+                labels: vec![],
+                priority: None,
+                project: Project {
+                    key: project_key.into(),
+                },
+                reporter: None,
+                summary: summary.into(),
+                custom: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// assigns the issue to a user by name, or an `Account`
+    pub fn assignee<A>(&mut self, assignee: A) -> &mut CreateIssueBuilder
+    where
+        A: Into<Account>,
+    {
+        self.fields.assignee = Some(assignee.into());
+        self
+    }
+
+    /// adds a component by name
+    pub fn component<C>(&mut self, name: C) -> &mut CreateIssueBuilder
+    where
+        C: Into<String>,
+    {
+        self.fields.components.push(Component { name: name.into() });
+        self
+    }
+
+    pub fn description<D>(&mut self, description: D) -> &mut CreateIssueBuilder
+    where
+        D: Into<String>,
+    {
+        self.fields.description = Some(description.into());
+        self
+    }
+
+    pub fn environment<E>(&mut self, environment: E) -> &mut CreateIssueBuilder
+    where
+        E: Into<String>,
+    {
+        self.fields.environment = Some(environment.into());
+        self
+    }
+
+    pub fn label<L>(&mut self, label: L) -> &mut CreateIssueBuilder
+    where
+        L: Into<String>,
+    {
+        self.fields.labels.push(label.into());
+        self
+    }
+
+    /// sets the priority by id
+    pub fn priority<P>(&mut self, id: P) -> &mut CreateIssueBuilder
+    where
+        P: Into<String>,
+    {
+        self.fields.priority = Some(Priority { id: id.into() });
+        self
+    }
+
+    /// sets the reporter by name, or an `Account`
+    pub fn reporter<R>(&mut self, reporter: R) -> &mut CreateIssueBuilder
+    where
+        R: Into<Account>,
+    {
+        self.fields.reporter = Some(reporter.into());
+        self
+    }
+
+    /// sets any other field, ie. a custom field, by field id
+    pub fn field<N, V>(&mut self, name: N, value: V) -> &mut CreateIssueBuilder
+    where
+        N: Into<String>,
+        V: Serialize,
+    {
+        self.fields.custom.insert(
+            name.into(),
+            serde_json::to_value(value).expect("Value to serialize"),
+        );
+        self
+    }
+
+    pub fn build(&self) -> CreateIssue {
+        CreateIssue {
+            fields: self.fields.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct CreateResponse {
     pub id: String,
@@ -64,6 +259,232 @@ pub struct CreateResponse {
     pub url: String,
 }
 
+/// an operation applied to a field with the `update` form of the edit api
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldOperation {
+    Set(Value),
+    Add(Value),
+    Remove(Value),
+    Edit(Value),
+}
+
+/// changes to apply to an existing issue
+/// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-editIssue
+///
+/// Typically this is initialized with EditIssue::builder()
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct EditIssue {
+    /// fields replaced with new values
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, Value>,
+    /// operations applied to fields, ie. adding a single label
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub update: BTreeMap<String, Vec<FieldOperation>>,
+    /// whether watchers are notified of the change. None leaves this to jira,
+    /// which notifies them
+    #[serde(skip)]
+    pub notify_users: Option<bool>,
+}
+
+impl EditIssue {
+    /// return a new instance of a builder for edits
+    pub fn builder() -> EditIssueBuilder {
+        EditIssueBuilder::new()
+    }
+}
+
+/// a builder interface for issue edits
+/// Typically this is initialized with EditIssue::builder()
+#[derive(Default, Debug)]
+pub struct EditIssueBuilder {
+    edit: EditIssue,
+}
+
+impl EditIssueBuilder {
+    pub fn new() -> EditIssueBuilder {
+        EditIssueBuilder {
+            edit: EditIssue::default(),
+        }
+    }
+
+    /// replaces the value of a field
+    pub fn field<N, V>(&mut self, name: N, value: V) -> &mut EditIssueBuilder
+    where
+        N: Into<String>,
+        V: Serialize,
+    {
+        self.edit.fields.insert(
+            name.into(),
+            serde_json::to_value(value).expect("Value to serialize"),
+        );
+        self
+    }
+
+    /// appends an operation to apply to a field
+    pub fn operation<N>(&mut self, name: N, operation: FieldOperation) -> &mut EditIssueBuilder
+    where
+        N: Into<String>,
+    {
+        self.edit
+            .update
+            .entry(name.into())
+            .or_default()
+            .push(operation);
+        self
+    }
+
+    pub fn summary<S>(&mut self, summary: S) -> &mut EditIssueBuilder
+    where
+        S: Into<String>,
+    {
+        self.field("summary", summary.into())
+    }
+
+    pub fn description<D>(&mut self, description: D) -> &mut EditIssueBuilder
+    where
+        D: Into<String>,
+    {
+        self.field("description", description.into())
+    }
+
+    /// replaces all labels
+    pub fn labels<L>(&mut self, labels: Vec<L>) -> &mut EditIssueBuilder
+    where
+        L: Into<String>,
+    {
+        self.field(
+            "labels",
+            labels.into_iter().map(Into::into).collect::<Vec<String>>(),
+        )
+    }
+
+    /// adds a label, keeping existing labels
+    pub fn add_label<L>(&mut self, label: L) -> &mut EditIssueBuilder
+    where
+        L: Into<String>,
+    {
+        self.operation("labels", FieldOperation::Add(Value::String(label.into())))
+    }
+
+    /// removes a label, keeping other labels
+    pub fn remove_label<L>(&mut self, label: L) -> &mut EditIssueBuilder
+    where
+        L: Into<String>,
+    {
+        self.operation(
+            "labels",
+            FieldOperation::Remove(Value::String(label.into())),
+        )
+    }
+
+    /// reassigns the issue to a user by name, or an `Account`. see
+    /// `Issues#assign` for unassigning issues
+    pub fn assignee<A>(&mut self, assignee: A) -> &mut EditIssueBuilder
+    where
+        A: Into<Account>,
+    {
+        self.field("assignee", assignee.into())
+    }
+
+    /// controls whether watchers are notified of the change. disabling
+    /// notifications requires admin or project admin permissions
+    pub fn notify_users(&mut self, notify: bool) -> &mut EditIssueBuilder {
+        self.edit.notify_users = Some(notify);
+        self
+    }
+
+    pub fn build(&self) -> EditIssue {
+        self.edit.clone()
+    }
+}
+
+/// the most issues jira creates with a single bulk request
+pub const BULK_CREATE_LIMIT: usize = 50;
+
+#[derive(Serialize, Debug)]
+pub(crate) struct BulkCreate<'a> {
+    #[serde(rename = "issueUpdates")]
+    pub issue_updates: &'a [CreateIssue],
+}
+
+/// the outcome of creating many issues, some of which may have failed
+#[derive(Deserialize, Debug, Default)]
+pub struct BulkCreateResponse {
+    /// the issues which were created, in the order they were requested
+    #[serde(default)]
+    pub issues: Vec<CreateResponse>,
+    #[serde(default)]
+    pub errors: Vec<BulkCreateError>,
+}
+
+impl BulkCreateResponse {
+    /// appends the results of a chunk of requested issues starting at `offset`
+    pub(crate) fn extend(&mut self, offset: usize, chunk: BulkCreateResponse) {
+        self.issues.extend(chunk.issues);
+        self.errors
+            .extend(chunk.errors.into_iter().map(|error| BulkCreateError {
+                failed_element_number: offset + error.failed_element_number,
+                ..error
+            }));
+    }
+
+    /// records every issue from `offset` as failed when the request for a chunk
+    /// fails outright, so issues created by earlier chunks are still reported.
+    /// issues after the failed chunk are never sent, and fail with the same error
+    pub(crate) fn fail(&mut self, offset: usize, requested: usize, error: &Error) {
+        let status = match *error {
+            Error::Fault { code, .. } | Error::Server { code, .. } => code.as_u16(),
+            _ => 0,
+        };
+        let message = error.to_string().trim().to_owned();
+        self.errors
+            .extend((offset..requested).map(|n| BulkCreateError {
+                status,
+                element_errors: Errors {
+                    error_messages: vec![message.clone()],
+                    errors: BTreeMap::new(),
+                },
+                failed_element_number: n,
+            }));
+    }
+}
+
+/// an issue which could not be created
+#[derive(Deserialize, Debug)]
+pub struct BulkCreateError {
+    /// the status of the failed request, or 0 when no response was received
+    pub status: u16,
+    #[serde(rename = "elementErrors")]
+    pub element_errors: Errors,
+    /// the index of the issue in the requested issues
+    #[serde(rename = "failedElementNumber")]
+    pub failed_element_number: usize,
+}
+
+/// recovers a bulk response from a request in which every issue failed.
+/// jira responds to these with a 400 carrying the bulk response, which
+/// isn't in its usual error format so it is kept as the only error message
+pub(crate) fn bulk_response(result: Result<BulkCreateResponse>) -> Result<BulkCreateResponse> {
+    match result {
+        Err(Error::Fault {
+            code: StatusCode::BAD_REQUEST,
+            errors,
+        }) => match errors
+            .error_messages
+            .first()
+            .and_then(|body| serde_json::from_str::<BulkCreateResponse>(body).ok())
+        {
+            Some(response) if !response.errors.is_empty() => Ok(response),
+            _ => Err(Error::Fault {
+                code: StatusCode::BAD_REQUEST,
+                errors,
+            }),
+        },
+        result => result,
+    }
+}
+
 #[derive(Deserialize, Debug)]
 pub struct IssueResults {
     pub expand: String,
@@ -90,6 +511,123 @@ impl Issues {
         self.jira.post("api", "/issue", data)
     }
 
+    /// creates many issues, in requests of up to `BULK_CREATE_LIMIT` issues.
+    /// issues which fail to be created are reported with their index
+    /// rather than failing the whole operation. when a request fails after
+    /// earlier requests created issues, the remaining issues are reported as
+    /// failed alongside those created. only a failure of the first request,
+    /// which created nothing, is returned as an error
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-createIssues
+    pub fn create_bulk(&self, issues: Vec<CreateIssue>) -> Result<BulkCreateResponse> {
+        let mut results = BulkCreateResponse::default();
+        for (n, chunk) in issues.chunks(BULK_CREATE_LIMIT).enumerate() {
+            let offset = n * BULK_CREATE_LIMIT;
+            match bulk_response(self.jira.post(
+                "api",
+                "/issue/bulk",
+                BulkCreate {
+                    issue_updates: chunk,
+                },
+            )) {
+                Ok(response) => results.extend(offset, response),
+                Err(e) if n == 0 => return Err(e),
+                Err(e) => {
+                    results.fail(offset, issues.len(), &e);
+                    break;
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    /// edits an existing issue. fields which fail validation are reported
+    /// as an `Error::InvalidFields` whose `errors` are keyed by field id
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-editIssue
+    pub fn update<I>(&self, id: I, edit: EditIssue) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .put::<EmptyResponse, _>("api", &edit_path(id.into(), &edit), &edit)
+            .map_err(field_errors)?;
+        Ok(())
+    }
+
+    /// assigns an issue to a user, or unassigns it when `assignee` is None.
+    /// `Account::automatic` assigns the issue to its project's default assignee
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-assign
+    pub fn assign<I>(&self, id: I, assignee: Option<Account>) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        self.jira.put::<EmptyResponse, _>(
+            "api",
+            &format!("/issue/{}/assignee", id.into()),
+            assignment(assignee),
+        )?;
+        Ok(())
+    }
+
+    /// deletes an issue. deleting an issue with subtasks fails with
+    /// `Error::IssueHasSubtasks` unless `delete_subtasks` is true
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-deleteIssue
+    pub fn delete<I>(&self, id: I, delete_subtasks: bool) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .delete::<EmptyResponse>("api", &delete_path(id.into(), delete_subtasks))
+            .map_err(|e| subtasks_error(e, delete_subtasks))?;
+        Ok(())
+    }
+
+    /// returns the issue types, with their fields, which may be created in
+    /// projects, by key. all projects are listed when no keys are given
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getCreateIssueMeta
+    pub fn create_meta<P>(&self, project_keys: Vec<P>) -> Result<CreateMeta>
+    where
+        P: Into<String>,
+    {
+        self.jira.get("api", &create_meta_path(project_keys))
+    }
+
+    /// returns a page of the issue types which may be created in a project,
+    /// without their fields. available from jira 8.4, which deprecates `create_meta`
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getCreateIssueMetaProjectIssueTypes
+    pub fn create_meta_issue_types(
+        &self,
+        project_key_or_id: &str,
+        options: &SearchOptions,
+    ) -> Result<IssueTypeMetaResults> {
+        self.jira
+            .get("api", &issue_types_path(project_key_or_id, options))
+    }
+
+    /// returns a page of the fields of an issue type which may be created in a project.
+    /// available from jira 8.4
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getCreateIssueMetaFields
+    pub fn create_meta_fields(
+        &self,
+        project_key_or_id: &str,
+        issuetype_id: &str,
+        options: &SearchOptions,
+    ) -> Result<FieldMetaResults> {
+        self.jira.get(
+            "api",
+            &fields_path(project_key_or_id, issuetype_id, options),
+        )
+    }
+
+    /// returns the fields of an issue which may be edited
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getEditIssueMeta
+    pub fn edit_meta<I>(&self, id: I) -> Result<EditMeta>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .get("api", &format!("/issue/{}/editmeta", id.into()))
+    }
+
     /// returns a single page of issues results
     /// https://docs.atlassian.com/jira-software/REST/latest/#agile/1.0/board-getIssuesForBoard
     pub fn list(&self, board: &Board, options: &SearchOptions) -> Result<IssueResults> {
@@ -110,6 +648,41 @@ impl Issues {
     }
 }
 
+/// the edit endpoint for an issue, with any notification preference
+pub(crate) fn edit_path(id: String, edit: &EditIssue) -> String {
+    match edit.notify_users {
+        Some(notify) => format!("/issue/{}?notifyUsers={}", id, notify),
+        None => format!("/issue/{}", id),
+    }
+}
+
+pub(crate) fn delete_path(id: String, delete_subtasks: bool) -> String {
+    format!("/issue/{}?deleteSubtasks={}", id, delete_subtasks)
+}
+
+/// distinguishes deletes rejected because the issue has subtasks, which
+/// can only happen when they weren't deleted too, from other bad requests
+pub(crate) fn subtasks_error(error: Error, delete_subtasks: bool) -> Error {
+    match error {
+        Error::Fault {
+            code: StatusCode::BAD_REQUEST,
+            errors,
+        } if !delete_subtasks && mentions_subtasks(&errors) => Error::IssueHasSubtasks(errors),
+        error => error,
+    }
+}
+
+fn mentions_subtasks(errors: &Errors) -> bool {
+    errors
+        .error_messages
+        .iter()
+        .chain(errors.errors.values())
+        .any(|message| {
+            let message = message.to_lowercase();
+            message.contains("subtask") || message.contains("sub-task")
+        })
+}
+
 /// provides an iterator over multiple pages of search results
 #[derive(Debug)]
 pub struct IssuesIter<'a> {
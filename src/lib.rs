@@ -6,45 +6,101 @@ extern crate reqwest;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+extern crate base64;
+#[cfg(feature = "async")]
+extern crate futures;
+extern crate httpdate;
+#[cfg(feature = "oauth")]
+extern crate openssl;
+#[cfg(feature = "oauth")]
+extern crate percent_encoding;
 extern crate serde_json;
 extern crate url;
 
-use std::io::Read;
+use std::io::{self, Read};
+use std::sync::Arc;
+use std::thread;
 
-use reqwest::header::CONTENT_TYPE;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, COOKIE, RETRY_AFTER};
 use reqwest::{blocking::Client, Method, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
+use crate::session::Session;
+
+#[cfg(feature = "async")]
+pub mod asynchronous;
+pub mod attachments;
 mod builder;
+pub mod cassette;
+pub mod comments;
 mod errors;
 pub mod issues;
+pub mod links;
+pub mod meta;
+#[cfg(feature = "mock")]
+pub mod mock;
+#[cfg(feature = "oauth")]
+pub mod oauth;
+pub mod remote_links;
 mod rep;
+mod retry;
 mod search;
+mod session;
 mod transitions;
+pub mod transport;
+pub mod votes;
+pub mod watchers;
+pub mod worklogs;
 
 pub use crate::builder::*;
 pub use crate::errors::*;
-pub use crate::issues::*;
+pub use crate::issues::{
+    Account, BulkCreateError, BulkCreateResponse, Component, CreateIssue, CreateIssueBuilder,
+    CreateResponse, EditIssue, EditIssueBuilder, FieldOperation, Fields, IssueResults, Issues,
+    IssuesIter, BULK_CREATE_LIMIT,
+};
 pub use crate::rep::*;
+pub use crate::retry::*;
 pub use crate::search::Search;
+pub use crate::session::SessionInfo;
 pub use crate::transitions::*;
+pub use crate::transport::{HttpRequest, HttpResponse, ReqwestTransport, Transport};
 pub mod boards;
 pub mod resolution;
 pub use crate::boards::*;
 pub mod sprints;
 pub use crate::sprints::*;
 
+/// represents a response with no content, such as a 204
 #[derive(Deserialize, Debug)]
 pub struct EmptyResponse;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Types of authentication credentials
+///
+/// Further kinds of credentials may be enabled by cargo features,
+/// ie. `OAuth` with the `oauth` feature
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum Credentials {
     /// username and password credentials
-    Basic(String, String), // todo: OAuth
+    Basic(String, String),
+    /// personal access token, sent as a bearer token.
+    /// Jira Data Center and Server 8.14+ accept these in place of a password
+    Bearer(String),
+    /// Jira Cloud account email and api token
+    /// https://id.atlassian.com/manage-profile/security/api-tokens
+    ApiToken(String, String),
+    /// OAuth 1.0a credentials for an application link consumer
+    /// see `oauth::OAuth` for obtaining an access token
+    #[cfg(feature = "oauth")]
+    OAuth(oauth::OAuthCredentials),
+    /// username and password used to log in once through `/rest/auth/1/session`.
+    /// the resulting session cookie is reused for subsequent requests and
+    /// renewed automatically when it expires
+    Session(String, String),
 }
 
 /// Entrypoint into client interface
@@ -53,7 +109,9 @@ pub enum Credentials {
 pub struct Jira {
     host: String,
     credentials: Credentials,
-    client: Client,
+    transport: Arc<dyn Transport>,
+    session: Session,
+    retry: RetryPolicy,
 }
 
 impl Jira {
@@ -64,8 +122,10 @@ impl Jira {
     {
         Ok(Jira {
             host: host.into(),
-            client: Client::new(),
+            transport: Arc::new(ReqwestTransport::default()),
             credentials,
+            session: Session::default(),
+            retry: RetryPolicy::none(),
         })
     }
 
@@ -73,14 +133,43 @@ impl Jira {
     pub fn from_client<H>(host: H, credentials: Credentials, client: Client) -> Result<Jira>
     where
         H: Into<String>,
+    {
+        Jira::from_transport(host, credentials, ReqwestTransport::new(client))
+    }
+
+    /// creates a new instance of a jira client which sends requests with a specified transport
+    pub fn from_transport<H, T>(host: H, credentials: Credentials, transport: T) -> Result<Jira>
+    where
+        H: Into<String>,
+        T: Transport + 'static,
     {
         Ok(Jira {
             host: host.into(),
             credentials,
-            client,
+            transport: Arc::new(transport),
+            session: Session::default(),
+            retry: RetryPolicy::none(),
         })
     }
 
+    /// sets the policy for retrying requests which fail transiently.
+    /// by default requests are not retried
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Jira {
+        self.retry = retry;
+        self
+    }
+
+    /// return oauth interface for obtaining an access token as an application link consumer.
+    /// token requests are sent with this client's transport and retry policy
+    #[cfg(feature = "oauth")]
+    pub fn oauth<C, K>(&self, consumer_key: C, private_key: K) -> oauth::OAuth
+    where
+        C: Into<String>,
+        K: Into<String>,
+    {
+        oauth::OAuth::from_jira(self, consumer_key, private_key)
+    }
+
     /// return transitions interface
     pub fn transitions<K>(&self, key: K) -> Transitions
     where
@@ -89,6 +178,53 @@ impl Jira {
         Transitions::new(self, key)
     }
 
+    /// return attachments interface
+    pub fn attachments(&self) -> attachments::Attachments {
+        attachments::Attachments::new(self)
+    }
+
+    /// return comments interface for an issue
+    pub fn comments<K>(&self, key: K) -> comments::Comments
+    where
+        K: Into<String>,
+    {
+        comments::Comments::new(self, key)
+    }
+
+    /// return issue links interface
+    pub fn links(&self) -> links::Links {
+        links::Links::new(self)
+    }
+
+    /// return remote links interface for an issue
+    pub fn remote_links<K>(&self, key: K) -> remote_links::RemoteLinks
+    where
+        K: Into<String>,
+    {
+        remote_links::RemoteLinks::new(self, key)
+    }
+
+    /// return votes interface for an issue
+    pub fn votes<K>(&self, key: K) -> votes::Votes
+    where
+        K: Into<String>,
+    {
+        votes::Votes::new(self, key)
+    }
+
+    /// return watchers interface for an issue
+    pub fn watchers<K>(&self, key: K) -> watchers::Watchers
+    where
+        K: Into<String>,
+    {
+        watchers::Watchers::new(self, key)
+    }
+
+    /// return worklogs interface
+    pub fn worklogs(&self) -> worklogs::Worklogs {
+        worklogs::Worklogs::new(self)
+    }
+
     /// return search interface
     pub fn search(&self) -> Search {
         Search::new(self)
@@ -109,7 +245,13 @@ impl Jira {
         Sprints::new(self)
     }
 
-    fn post<D, S>(&self, api_name: &str, endpoint: &str, body: S) -> Result<D>
+    /// ends the current session when using `Credentials::Session`
+    pub fn logout(&self) -> Result<()> {
+        self.session.logout(self.transport.as_ref(), &self.host)
+    }
+
+    /// sends a POST request with a json body to an endpoint of the given api, ie. "api" or "agile"
+    pub fn post<D, S>(&self, api_name: &str, endpoint: &str, body: S) -> Result<D>
     where
         D: DeserializeOwned,
         S: Serialize,
@@ -119,13 +261,46 @@ impl Jira {
         self.request::<D>(Method::POST, api_name, endpoint, Some(data.into_bytes()))
     }
 
-    fn get<D>(&self, api_name: &str, endpoint: &str) -> Result<D>
+    /// sends a PUT request with a json body to an endpoint of the given api
+    pub fn put<D, S>(&self, api_name: &str, endpoint: &str, body: S) -> Result<D>
+    where
+        D: DeserializeOwned,
+        S: Serialize,
+    {
+        let data = serde_json::to_string::<S>(&body)?;
+        debug!("Json request: {}", data);
+        self.request::<D>(Method::PUT, api_name, endpoint, Some(data.into_bytes()))
+    }
+
+    /// sends a PATCH request with a json body to an endpoint of the given api
+    pub fn patch<D, S>(&self, api_name: &str, endpoint: &str, body: S) -> Result<D>
+    where
+        D: DeserializeOwned,
+        S: Serialize,
+    {
+        let data = serde_json::to_string::<S>(&body)?;
+        debug!("Json request: {}", data);
+        self.request::<D>(Method::PATCH, api_name, endpoint, Some(data.into_bytes()))
+    }
+
+    /// sends a GET request to an endpoint of the given api
+    pub fn get<D>(&self, api_name: &str, endpoint: &str) -> Result<D>
     where
         D: DeserializeOwned,
     {
         self.request::<D>(Method::GET, api_name, endpoint, None)
     }
 
+    /// sends a DELETE request to an endpoint of the given api
+    ///
+    /// endpoints which respond with 204 No Content may be deserialized into `()` or `EmptyResponse`
+    pub fn delete<D>(&self, api_name: &str, endpoint: &str) -> Result<D>
+    where
+        D: DeserializeOwned,
+    {
+        self.request::<D>(Method::DELETE, api_name, endpoint, None)
+    }
+
     fn request<D>(
         &self,
         method: Method,
@@ -136,36 +311,181 @@ impl Jira {
     where
         D: DeserializeOwned,
     {
-        let url = format!("{}/rest/{}/latest{}", self.host, api_name, endpoint);
+        let url = self.url(api_name, endpoint);
         debug!("url -> {:?}", url);
 
-        let req = self.client.request(method, &url);
-        let builder = match self.credentials {
-            Credentials::Basic(ref user, ref pass) => req
-                .basic_auth(user.to_owned(), Some(pass.to_owned()))
-                .header(CONTENT_TYPE, "application/json"),
-        };
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        read_response(self.execute(method, &url, headers, body)?)
+    }
 
-        let mut res = match body {
-            Some(bod) => builder.body(bod).send()?,
-            _ => builder.send()?,
-        };
+    /// the url of an endpoint of the given api
+    pub(crate) fn url(&self, api_name: &str, endpoint: &str) -> String {
+        format!("{}/rest/{}/latest{}", self.host, api_name, endpoint)
+    }
+
+    /// sends a request with credentials, retrying transient failures and logging in
+    /// again when a session has expired. the response body is left unread
+    pub(crate) fn execute(
+        &self,
+        method: Method,
+        url: &str,
+        headers: HeaderMap,
+        body: Option<Vec<u8>>,
+    ) -> Result<HttpResponse> {
+        let res = self.send(method.clone(), url, headers.clone(), body.clone())?;
+        match self.credentials {
+            // the session may have expired, so log in again and retry once
+            Credentials::Session(ref user, ref pass) if res.status == StatusCode::UNAUTHORIZED => {
+                self.session.invalidate();
+                self.login(user, pass)?;
+                self.send(method, url, headers, body)
+            }
+            _ => Ok(res),
+        }
+    }
+
+    fn send(
+        &self,
+        method: Method,
+        url: &str,
+        headers: HeaderMap,
+        body: Option<Vec<u8>>,
+    ) -> Result<HttpResponse> {
+        let mut headers = headers;
+        if let Credentials::Session(ref user, ref pass) = self.credentials {
+            let cookie = match self.session.cookie() {
+                Some(cookie) => cookie,
+                None => self.login(user, pass)?,
+            };
+            headers.insert(COOKIE, header_value(cookie)?);
+        }
+        self.retrying(self.retry.retries(&method), || {
+            let mut headers = headers.clone();
+            if let Some(authorization) = self.credentials.authorization(&method, url)? {
+                headers.insert(AUTHORIZATION, header_value(authorization)?);
+            }
+            Ok(HttpRequest {
+                method: method.clone(),
+                url: url.to_owned(),
+                headers,
+                body: body.clone(),
+            })
+        })
+    }
+
+    /// logs in and stores the issued session cookie
+    fn login(&self, user: &str, pass: &str) -> Result<String> {
+        let request = Session::request(&self.host, user, pass)?;
+        let mut res = self.retrying(true, || Ok(request.clone()))?;
 
         let mut body = String::new();
-        res.read_to_string(&mut body)?;
-        debug!("status {:?} body '{:?}'", res.status(), body);
-        match res.status() {
-            StatusCode::UNAUTHORIZED => Err(Error::Unauthorized),
-            StatusCode::METHOD_NOT_ALLOWED => Err(Error::MethodNotAllowed),
-            StatusCode::NOT_FOUND => Err(Error::NotFound),
-            client_err if client_err.is_client_error() => Err(Error::Fault {
-                code: res.status(),
-                errors: serde_json::from_str::<Errors>(&body)?,
-            }),
-            _ => {
-                let data = if body == "" { "null" } else { &body };
-                Ok(serde_json::from_str::<D>(data)?)
+        res.body.read_to_string(&mut body)?;
+        debug!("status {:?} body '{:?}'", res.status, body);
+        self.session.store(res.status, &res.headers, &body)
+    }
+
+    /// sends the request built by `request`, building and sending it again while
+    /// the retry policy allows. `retried` is false for requests which are never retried
+    fn retrying<F>(&self, retried: bool, request: F) -> Result<HttpResponse>
+    where
+        F: Fn() -> Result<HttpRequest>,
+    {
+        let mut attempt = 1;
+        loop {
+            let result = self.transport.send(request()?);
+            let res = match result {
+                Ok(res) => res,
+                Err(e) => match self.retry.delay(attempt, None, None).filter(|_| retried) {
+                    Some(delay) => {
+                        debug!("retrying in {:?} after error {}", delay, e);
+                        thread::sleep(delay);
+                        attempt += 1;
+                        continue;
+                    }
+                    None => return Err(e),
+                },
+            };
+
+            let retry_after = res
+                .headers
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok());
+            if let Some(delay) = self
+                .retry
+                .delay(attempt, Some(res.status), retry_after)
+                .filter(|_| retried)
+            {
+                debug!("retrying in {:?} after status {:?}", delay, res.status);
+                thread::sleep(delay);
+                attempt += 1;
+                continue;
             }
+            return Ok(res);
+        }
+    }
+}
+
+/// reads a response body, mapping it onto an error or a deserialized value
+pub(crate) fn read_response<D>(mut res: HttpResponse) -> Result<D>
+where
+    D: DeserializeOwned,
+{
+    let mut body = String::new();
+    res.body.read_to_string(&mut body)?;
+    debug!("status {:?} body '{:?}'", res.status, body);
+    parse_response(res.status, &res.headers, &body)
+}
+
+/// converts a credential into a header value
+pub(crate) fn header_value(value: String) -> Result<HeaderValue> {
+    HeaderValue::from_str(&value)
+        .map_err(|e| Error::IO(io::Error::new(io::ErrorKind::InvalidInput, e)))
+}
+
+impl Credentials {
+    /// returns the `Authorization` header value, if any, for a request.
+    /// session credentials authenticate with a cookie instead
+    #[cfg_attr(not(feature = "oauth"), allow(unused_variables))]
+    fn authorization(&self, method: &Method, url: &str) -> Result<Option<String>> {
+        let basic = |user: &str, pass: &str| {
+            format!("Basic {}", base64::encode(format!("{}:{}", user, pass)))
+        };
+        Ok(match *self {
+            Credentials::Basic(ref user, ref pass) => Some(basic(user, pass)),
+            Credentials::Bearer(ref token) => Some(format!("Bearer {}", token)),
+            Credentials::ApiToken(ref email, ref token) => Some(basic(email, token)),
+            #[cfg(feature = "oauth")]
+            Credentials::OAuth(ref oauth) => Some(oauth.authorization(method, url, &[])?),
+            Credentials::Session(..) => None,
+        })
+    }
+}
+
+/// maps a response status and body onto an error or a deserialized value
+fn parse_response<D>(status: StatusCode, headers: &HeaderMap, body: &str) -> Result<D>
+where
+    D: DeserializeOwned,
+{
+    match status {
+        StatusCode::UNAUTHORIZED => Err(Error::Unauthorized),
+        StatusCode::METHOD_NOT_ALLOWED => Err(Error::MethodNotAllowed),
+        StatusCode::NOT_FOUND => Err(Error::NotFound),
+        client_err if client_err.is_client_error() => Err(Error::Fault {
+            code: status,
+            errors: Errors::from_body(body),
+        }),
+        server_err if server_err.is_server_error() => Err(Error::Server {
+            code: status,
+            headers: headers.clone(),
+            body: body.to_owned(),
+        }),
+        // 204 responses carry no content, so treat them as a json null
+        // which deserializes into `()` or `EmptyResponse`
+        StatusCode::NO_CONTENT => Ok(serde_json::from_str::<D>("null")?),
+        _ => {
+            let data = if body.is_empty() { "null" } else { body };
+            Ok(serde_json::from_str::<D>(data)?)
         }
     }
 }
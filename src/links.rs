@@ -0,0 +1,114 @@
+//! Interfaces for linking issues
+
+// Ours
+use crate::comments::AddComment;
+use crate::{EmptyResponse, IssueLink, Jira, LinkType, Result};
+
+/// issue links interface
+#[derive(Debug)]
+pub struct Links {
+    jira: Jira,
+}
+
+/// an issue, identified by key
+#[derive(Serialize, Debug, Clone)]
+pub struct LinkedIssue {
+    pub key: String,
+}
+
+/// a type of link, identified by name
+#[derive(Serialize, Debug, Clone)]
+pub struct LinkTypeName {
+    pub name: String,
+}
+
+/// a new link between two issues
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateIssueLink {
+    #[serde(rename = "type")]
+    pub link_type: LinkTypeName,
+    pub inward_issue: LinkedIssue,
+    pub outward_issue: LinkedIssue,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<AddComment>,
+}
+
+impl CreateIssueLink {
+    /// links an inward and outward issue with a link type, by name, ie. "Blocks".
+    /// the names of each side of a link type are listed by `Links#types`
+    pub fn new<T, I, O>(link_type: T, inward_key: I, outward_key: O) -> CreateIssueLink
+    where
+        T: Into<String>,
+        I: Into<String>,
+        O: Into<String>,
+    {
+        CreateIssueLink {
+            link_type: LinkTypeName {
+                name: link_type.into(),
+            },
+            inward_issue: LinkedIssue {
+                key: inward_key.into(),
+            },
+            outward_issue: LinkedIssue {
+                key: outward_key.into(),
+            },
+            comment: None,
+        }
+    }
+
+    /// adds a comment along with the link
+    pub fn comment(mut self, comment: AddComment) -> CreateIssueLink {
+        self.comment = Some(comment);
+        self
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkTypes {
+    pub issue_link_types: Vec<LinkType>,
+}
+
+impl Links {
+    pub fn new(jira: &Jira) -> Links {
+        Links { jira: jira.clone() }
+    }
+
+    /// links two issues. jira doesn't return the new link, which
+    /// is listed by `Issue#links` on either issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issueLink-linkIssues
+    pub fn create(&self, link: CreateIssueLink) -> Result<()> {
+        self.jira
+            .post::<EmptyResponse, _>("api", "/issueLink", link)?;
+        Ok(())
+    }
+
+    /// returns a link between two issues
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issueLink-getIssueLink
+    pub fn get<I>(&self, id: I) -> Result<IssueLink>
+    where
+        I: Into<String>,
+    {
+        self.jira.get("api", &format!("/issueLink/{}", id.into()))
+    }
+
+    /// removes a link between two issues
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issueLink-deleteIssueLink
+    pub fn delete<I>(&self, id: I) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        self.jira
+            .delete::<EmptyResponse>("api", &format!("/issueLink/{}", id.into()))?;
+        Ok(())
+    }
+
+    /// returns the types of links which may be created
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issueLinkType-getIssueLinkTypes
+    pub fn types(&self) -> Result<Vec<LinkType>> {
+        self.jira
+            .get::<LinkTypes>("api", "/issueLinkType")
+            .map(|types| types.issue_link_types)
+    }
+}
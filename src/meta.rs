@@ -0,0 +1,377 @@
+//! Metadata describing the fields of issues which may be created or edited
+
+// Third party
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use url::form_urlencoded;
+
+// Ours
+use crate::issues::{CreateIssue, EditIssue, FieldOperation};
+use crate::SearchOptions;
+
+/// the issue types, and their fields, which may be created in projects
+/// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getCreateIssueMeta
+#[derive(Deserialize, Debug, Clone)]
+pub struct CreateMeta {
+    pub projects: Vec<ProjectMeta>,
+}
+
+impl CreateMeta {
+    /// returns the metadata of an issue type, by id, within a project, by key
+    pub fn issue_type(&self, project_key: &str, issuetype_id: &str) -> Option<&IssueTypeMeta> {
+        self.projects
+            .iter()
+            .find(|project| project.key == project_key)
+            .and_then(|project| {
+                project
+                    .issuetypes
+                    .iter()
+                    .find(|issuetype| issuetype.id == issuetype_id)
+            })
+    }
+
+    /// checks a new issue against the metadata of its project and issue type
+    pub fn validate(&self, issue: &CreateIssue) -> Vec<Problem> {
+        let fields = &issue.fields;
+        if !self
+            .projects
+            .iter()
+            .any(|project| project.key == fields.project.key)
+        {
+            return vec![Problem::new(
+                "project",
+                ProblemKind::NotAllowed(Value::String(fields.project.key.clone())),
+            )];
+        }
+        match self.issue_type(&fields.project.key, &fields.issuetype.id) {
+            Some(issuetype) => issuetype.validate(issue),
+            None => vec![Problem::new(
+                "issuetype",
+                ProblemKind::NotAllowed(Value::String(fields.issuetype.id.clone())),
+            )],
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProjectMeta {
+    pub id: String,
+    pub key: String,
+    pub name: String,
+    #[serde(default)]
+    pub issuetypes: Vec<IssueTypeMeta>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct IssueTypeMeta {
+    pub id: String,
+    pub name: String,
+    pub subtask: bool,
+    pub description: Option<String>,
+    /// fields by id. only listed when createmeta is expanded with fields,
+    /// and never by `Issues#create_meta_issue_types`
+    #[serde(default)]
+    pub fields: BTreeMap<String, FieldMeta>,
+}
+
+impl IssueTypeMeta {
+    /// checks a new issue against the fields of this issue type
+    pub fn validate(&self, issue: &CreateIssue) -> Vec<Problem> {
+        validate_create(&self.fields, issue)
+    }
+}
+
+/// describes a field of an issue
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldMeta {
+    /// the field's id, which is only listed by `Issues#create_meta_fields`
+    pub field_id: Option<String>,
+    pub name: String,
+    pub required: bool,
+    pub schema: FieldSchema,
+    #[serde(default)]
+    pub operations: Vec<String>,
+    /// the values a field may be set to, when limited to a fixed set
+    pub allowed_values: Option<Vec<Value>>,
+    pub has_default_value: Option<bool>,
+    pub default_value: Option<Value>,
+    pub auto_complete_url: Option<String>,
+}
+
+impl FieldMeta {
+    /// returns the problem with a value of this field, if any
+    fn check(&self, value: &Value) -> Option<ProblemKind> {
+        if is_empty(value) {
+            return if self.required {
+                Some(ProblemKind::Missing)
+            } else {
+                None
+            };
+        }
+        match (self.schema.schema_type.as_str(), value.as_array()) {
+            ("array", Some(items)) => items.iter().find_map(|item| self.check_item(item)),
+            (schema_type, _) => self.check_scalar(schema_type, value),
+        }
+    }
+
+    /// returns the problem with a value added to or removed from this field, if any
+    fn check_item(&self, value: &Value) -> Option<ProblemKind> {
+        match (self.schema.schema_type.as_str(), self.schema.items.as_ref()) {
+            ("array", Some(items)) => self.check_scalar(items, value),
+            ("array", None) => None,
+            (schema_type, _) => self.check_scalar(schema_type, value),
+        }
+    }
+
+    fn check_scalar(&self, schema_type: &str, value: &Value) -> Option<ProblemKind> {
+        let matches = match schema_type {
+            "string" | "date" | "datetime" => value.is_string(),
+            "number" => value.is_number(),
+            "array" => value.is_array(),
+            "any" => true,
+            // everything else, ie. options, users and priorities, is referenced by an object
+            _ => value.is_object(),
+        };
+        if !matches {
+            return Some(ProblemKind::WrongType {
+                expected: schema_type.to_owned(),
+            });
+        }
+        match self.allowed_values {
+            Some(ref allowed) if value.is_object() => {
+                if allowed.iter().any(|allowed| same_reference(allowed, value)) {
+                    None
+                } else {
+                    Some(ProblemKind::NotAllowed(value.clone()))
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// the type of a field's value
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldSchema {
+    /// ie. "string", "number", "array", "option", "user" or "priority"
+    #[serde(rename = "type")]
+    pub schema_type: String,
+    /// the type of each item of an array
+    pub items: Option<String>,
+    /// the name of a system field
+    pub system: Option<String>,
+    /// the type key of a custom field
+    pub custom: Option<String>,
+    pub custom_id: Option<u64>,
+}
+
+/// a page of the issue types which may be created in a project
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueTypeMetaResults {
+    pub start_at: u64,
+    pub max_results: u64,
+    pub total: u64,
+    pub values: Vec<IssueTypeMeta>,
+}
+
+/// a page of the fields of an issue type which may be created in a project
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldMetaResults {
+    pub start_at: u64,
+    pub max_results: u64,
+    pub total: u64,
+    pub values: Vec<FieldMeta>,
+}
+
+/// the fields of an issue which may be edited
+/// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getEditIssueMeta
+#[derive(Deserialize, Debug, Clone)]
+pub struct EditMeta {
+    pub fields: BTreeMap<String, FieldMeta>,
+}
+
+impl EditMeta {
+    /// checks changes to an issue against the fields which may be edited
+    pub fn validate(&self, edit: &EditIssue) -> Vec<Problem> {
+        let mut problems = vec![];
+        for (id, value) in &edit.fields {
+            let problem = match self.fields.get(id) {
+                Some(meta) => meta.check(value),
+                None => Some(ProblemKind::Unknown),
+            };
+            problems.extend(problem.map(|kind| Problem::new(id.as_str(), kind)));
+        }
+        for (id, operations) in &edit.update {
+            let meta = match self.fields.get(id) {
+                Some(meta) => meta,
+                None => {
+                    problems.push(Problem::new(id.as_str(), ProblemKind::Unknown));
+                    continue;
+                }
+            };
+            for operation in operations {
+                let (name, problem) = match *operation {
+                    FieldOperation::Set(ref value) => ("set", meta.check(value)),
+                    FieldOperation::Add(ref value) => ("add", meta.check_item(value)),
+                    FieldOperation::Remove(ref value) => ("remove", meta.check_item(value)),
+                    FieldOperation::Edit(_) => ("edit", None),
+                };
+                let problem = if meta.operations.iter().any(|op| op == name) {
+                    problem
+                } else {
+                    Some(ProblemKind::UnsupportedOperation(name.to_owned()))
+                };
+                problems.extend(problem.map(|kind| Problem::new(id.as_str(), kind)));
+            }
+        }
+        problems
+    }
+}
+
+/// a problem with a field of an issue which jira would reject
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    /// the field's id, ie. "summary" or "customfield_10010"
+    pub field: String,
+    pub kind: ProblemKind,
+}
+
+impl Problem {
+    fn new(field: &str, kind: ProblemKind) -> Problem {
+        Problem {
+            field: field.to_owned(),
+            kind,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProblemKind {
+    /// a required field has no value
+    Missing,
+    /// the field can't be set, ie. because it isn't on the issue type's screen
+    Unknown,
+    /// the value isn't of the field's schema type
+    WrongType { expected: String },
+    /// the value isn't one of the field's allowed values
+    NotAllowed(Value),
+    /// the field doesn't support an update operation, ie. "add"
+    UnsupportedOperation(String),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ProblemKind::Missing => write!(f, "{} is required", self.field),
+            ProblemKind::Unknown => write!(f, "{} can not be set", self.field),
+            ProblemKind::WrongType { ref expected } => {
+                write!(f, "{} should be of type {}", self.field, expected)
+            }
+            ProblemKind::NotAllowed(ref value) => {
+                write!(f, "{} is not an allowed value of {}", value, self.field)
+            }
+            ProblemKind::UnsupportedOperation(ref operation) => {
+                write!(f, "{} does not support {}", self.field, operation)
+            }
+        }
+    }
+}
+
+/// checks a new issue against the fields of its issue type, by id. fields
+/// listed by `Issues#create_meta_fields` may be collected by their `field_id`
+pub fn validate_create(fields: &BTreeMap<String, FieldMeta>, issue: &CreateIssue) -> Vec<Problem> {
+    let values = match serde_json::to_value(&issue.fields) {
+        Ok(Value::Object(values)) => values,
+        _ => Default::default(),
+    };
+    let mut problems = vec![];
+    for (id, meta) in fields {
+        // jira fills in fields with default values when they're left out
+        if meta.required && !meta.has_default_value.unwrap_or(false) && !values.contains_key(id) {
+            problems.push(Problem::new(id.as_str(), ProblemKind::Missing));
+        }
+    }
+    for (id, value) in &values {
+        let problem = match fields.get(id) {
+            Some(meta) => meta.check(value),
+            None => Some(ProblemKind::Unknown),
+        };
+        problems.extend(problem.map(|kind| Problem::new(id.as_str(), kind)));
+    }
+    problems
+}
+
+fn is_empty(value: &Value) -> bool {
+    match *value {
+        Value::Null => true,
+        Value::String(ref s) => s.is_empty(),
+        Value::Array(ref values) => values.is_empty(),
+        _ => false,
+    }
+}
+
+/// whether a value references an allowed value by any of the properties
+/// jira identifies values with
+fn same_reference(allowed: &Value, value: &Value) -> bool {
+    let scalar = |value: &Value| match *value {
+        Value::String(ref s) => Some(s.clone()),
+        Value::Number(ref n) => Some(n.to_string()),
+        _ => None,
+    };
+    ["id", "key", "name", "value"].iter().any(|property| {
+        match (
+            value.get(property).and_then(scalar),
+            allowed.get(property).and_then(scalar),
+        ) {
+            (Some(value), Some(allowed)) => value == allowed,
+            _ => false,
+        }
+    })
+}
+
+pub(crate) fn create_meta_path<P>(project_keys: Vec<P>) -> String
+where
+    P: Into<String>,
+{
+    let keys = project_keys
+        .into_iter()
+        .map(|key| key.into())
+        .collect::<Vec<String>>();
+    let mut query = form_urlencoded::Serializer::new(String::new());
+    if !keys.is_empty() {
+        query.append_pair("projectKeys", &keys.join(","));
+    }
+    format!(
+        "/issue/createmeta?{}",
+        query
+            .append_pair("expand", "projects.issuetypes.fields")
+            .finish()
+    )
+}
+
+pub(crate) fn issue_types_path(project_key_or_id: &str, options: &SearchOptions) -> String {
+    let mut path = vec![format!(
+        "/issue/createmeta/{}/issuetypes",
+        project_key_or_id
+    )];
+    path.extend(options.serialize());
+    path.join("?")
+}
+
+pub(crate) fn fields_path(
+    project_key_or_id: &str,
+    issuetype_id: &str,
+    options: &SearchOptions,
+) -> String {
+    let mut path = vec![format!(
+        "/issue/createmeta/{}/issuetypes/{}",
+        project_key_or_id, issuetype_id
+    )];
+    path.extend(options.serialize());
+    path.join("?")
+}
@@ -0,0 +1,1002 @@
+//! An in-memory mock Jira server for integration tests
+//!
+//! `MockServer` listens on a local port and implements the subset of the
+//! `/rest/api` and `/rest/agile` endpoints goji uses, backed by in-memory state.
+//! Point a `Jira` client at `MockServer#url` to test whole workflows without a
+//! live Jira. Enabled with the `mock` cargo feature.
+//!
+//! ```
+//! # extern crate goji;
+//! use goji::mock::MockServer;
+//! use goji::{CreateIssue, Credentials, Jira};
+//!
+//! let server = MockServer::start().unwrap();
+//! server.add_project("TEST", "Test project");
+//! let jira = Jira::new(server.url(), Credentials::Basic("user".into(), "pwd".into())).unwrap();
+//!
+//! let created = jira
+//!     .issues()
+//!     .create(CreateIssue::builder("TEST", "10001", "write tests").build())
+//!     .unwrap();
+//! assert_eq!(created.key, "TEST-1");
+//! ```
+//!
+//! Supported endpoints
+//!
+//! * `POST /rest/auth/1/session` and `DELETE /rest/auth/1/session`
+//! * `POST /issue`, `GET`, `PUT` and `DELETE /issue/{key}`. issues with a `parent`
+//!   field are subtasks of that issue
+//! * `GET` and `POST /issue/{key}/transitions`
+//! * `GET /search` with basic JQL: `=`, `!=`, `~`, `in`, `not in`, `is empty`,
+//!   `is not empty` clauses joined with `and` or `or`. `order by` is ignored
+//! * `GET /board`, `GET /board/{id}`, `GET /board/{id}/issue` and `GET /board/{id}/sprint`
+//! * `POST /sprint/{id}/issue`
+//!
+//! Issue types are "Task" (10001), "Bug" (10002), "Story" (10003) and "Sub-task" (10004).
+//! Issues move through a fixed workflow: "To Do" -(11 Start Progress)-> "In Progress"
+//! -(21 Done)-> "Done" -(31 Reopen)-> "To Do".
+
+// Third party
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use url::form_urlencoded;
+
+/// (id, name, category) of each status in the mock workflow
+const STATUSES: &[(&str, &str, &str)] = &[
+    ("1", "To Do", "new"),
+    ("3", "In Progress", "indeterminate"),
+    ("5", "Done", "done"),
+];
+
+/// (id, key, name, color) of each status category
+const STATUS_CATEGORIES: &[(u64, &str, &str, &str)] = &[
+    (2, "new", "To Do", "blue-gray"),
+    (4, "indeterminate", "In Progress", "yellow"),
+    (3, "done", "Done", "green"),
+];
+
+/// (id, name, from status id, to status id) of each transition in the mock workflow
+const TRANSITIONS: &[(&str, &str, &str, &str)] = &[
+    ("11", "Start Progress", "1", "3"),
+    ("21", "Done", "3", "5"),
+    ("31", "Reopen", "5", "1"),
+];
+
+/// (id, name, subtask) of each issue type
+const ISSUE_TYPES: &[(&str, &str, bool)] = &[
+    ("10001", "Task", false),
+    ("10002", "Bug", false),
+    ("10003", "Story", false),
+    ("10004", "Sub-task", true),
+];
+
+#[derive(Debug, Default)]
+struct State {
+    base: String,
+    projects: Vec<(String, String, String)>,
+    issues: Vec<Value>,
+    issue_counter: u64,
+    keys: BTreeMap<String, u64>,
+    boards: Vec<(u64, String, String)>,
+    sprints: Vec<(u64, u64, String, String)>,
+    sprint_issues: BTreeMap<String, u64>,
+    next_id: u64,
+}
+
+/// a local http server implementing a subset of the jira rest api
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl MockServer {
+    /// starts a new server on a free local port
+    pub fn start() -> io::Result<MockServer> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State {
+            base: format!("http://{}", addr),
+            next_id: 10000,
+            ..Default::default()
+        }));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let (handler_state, handler_shutdown) = (state.clone(), shutdown.clone());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if handler_shutdown.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    let state = handler_state.clone();
+                    thread::spawn(move || {
+                        if let Err(e) = handle(stream, &state) {
+                            debug!("mock server connection error {}", e);
+                        }
+                    });
+                }
+            }
+        });
+
+        Ok(MockServer {
+            addr,
+            state,
+            shutdown,
+        })
+    }
+
+    /// the base url of the server, to be used as a jira host
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// adds a project which issues may be created in
+    pub fn add_project<K, N>(&self, key: K, name: N)
+    where
+        K: Into<String>,
+        N: Into<String>,
+    {
+        let mut state = self.state.lock().unwrap();
+        let id = state.id();
+        state
+            .projects
+            .push((id.to_string(), key.into(), name.into()));
+    }
+
+    /// adds a scrum board for a project, returning its id
+    pub fn add_board<N, P>(&self, name: N, project_key: P) -> u64
+    where
+        N: Into<String>,
+        P: Into<String>,
+    {
+        let mut state = self.state.lock().unwrap();
+        let id = state.boards.len() as u64 + 1;
+        state.boards.push((id, name.into(), project_key.into()));
+        id
+    }
+
+    /// adds an active sprint to a board, returning its id
+    pub fn add_sprint<N>(&self, board_id: u64, name: N) -> u64
+    where
+        N: Into<String>,
+    {
+        let mut state = self.state.lock().unwrap();
+        let id = state.sprints.len() as u64 + 1;
+        state
+            .sprints
+            .push((id, board_id, name.into(), "active".to_owned()));
+        id
+    }
+
+    /// returns the current json representation of an issue
+    pub fn issue(&self, key: &str) -> Option<Value> {
+        let state = self.state.lock().unwrap();
+        state.find(key).map(|index| state.issues[index].clone())
+    }
+
+    /// returns the id of the sprint an issue was moved into, if any
+    pub fn sprint_of(&self, key: &str) -> Option<u64> {
+        self.state.lock().unwrap().sprint_issues.get(key).cloned()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // wake the listener so it notices the shutdown
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: BTreeMap<String, String>,
+    body: Value,
+}
+
+struct Response {
+    status: u16,
+    body: Option<Value>,
+}
+
+impl Response {
+    fn json(status: u16, body: Value) -> Response {
+        Response {
+            status,
+            body: Some(body),
+        }
+    }
+
+    fn no_content() -> Response {
+        Response {
+            status: 204,
+            body: None,
+        }
+    }
+
+    fn not_found(message: &str) -> Response {
+        Response::json(404, json!({ "errorMessages": [message], "errors": {} }))
+    }
+
+    fn bad_request(field: &str, message: &str) -> Response {
+        Response::json(
+            400,
+            json!({ "errorMessages": [], "errors": { field: message } }),
+        )
+    }
+}
+
+fn handle(stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let target = parts.next().unwrap_or_default().to_owned();
+
+    let mut length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line
+            .to_lowercase()
+            .strip_prefix("content-length:")
+            .map(|value| value.trim().to_owned())
+        {
+            length = value.parse().unwrap_or(0);
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    let (path, query) = match target.find('?') {
+        Some(index) => (target[..index].to_owned(), &target[index + 1..]),
+        None => (target.clone(), ""),
+    };
+    let request = Request {
+        method,
+        path,
+        query: form_urlencoded::parse(query.as_bytes())
+            .into_owned()
+            .collect(),
+        body: serde_json::from_slice(&body).unwrap_or(Value::Null),
+    };
+    debug!("mock server {} {}", request.method, target);
+
+    let response = route(&request, &mut state.lock().unwrap());
+    let body = response
+        .body
+        .map(|body| body.to_string())
+        .unwrap_or_default();
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        reason(response.status),
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "",
+    }
+}
+
+fn route(request: &Request, state: &mut State) -> Response {
+    let segments = request
+        .path
+        .trim_matches('/')
+        .split('/')
+        .collect::<Vec<_>>();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("POST", ["rest", "auth", _, "session"]) => Response::json(
+            200,
+            json!({ "session": { "name": "JSESSIONID", "value": "mock-session" } }),
+        ),
+        ("DELETE", ["rest", "auth", _, "session"]) => Response::no_content(),
+        (method, ["rest", "api", _, rest @ ..]) => api(method, rest, request, state),
+        (method, ["rest", "agile", _, rest @ ..]) => agile(method, rest, request, state),
+        _ => Response::not_found("no such endpoint"),
+    }
+}
+
+fn api(method: &str, path: &[&str], request: &Request, state: &mut State) -> Response {
+    match (method, path) {
+        ("POST", ["issue"]) => state.create_issue(&request.body),
+        ("GET", ["issue", key]) => match state.find(key) {
+            Some(index) => Response::json(200, state.issues[index].clone()),
+            None => Response::not_found("Issue Does Not Exist"),
+        },
+        ("PUT", ["issue", key]) => state.edit_issue(key, &request.body),
+        ("DELETE", ["issue", key]) => {
+            let delete_subtasks = request
+                .query
+                .get("deleteSubtasks")
+                .is_some_and(|value| value == "true");
+            state.delete_issue(key, delete_subtasks)
+        }
+        ("GET", ["issue", key, "transitions"]) => match state.find(key) {
+            Some(index) => {
+                let status = state.issues[index]["fields"]["status"]["id"].clone();
+                let transitions = TRANSITIONS
+                    .iter()
+                    .filter(|(_, _, from, _)| status == *from)
+                    .map(|(id, name, _, to)| {
+                        json!({ "id": id, "name": name, "to": state.status(to) })
+                    })
+                    .collect::<Vec<_>>();
+                Response::json(200, json!({ "transitions": transitions }))
+            }
+            None => Response::not_found("Issue Does Not Exist"),
+        },
+        ("POST", ["issue", key, "transitions"]) => state.transition(key, &request.body),
+        ("GET", ["search"]) => {
+            let jql = request.query.get("jql").cloned().unwrap_or_default();
+            let matches = match parse_jql(&jql) {
+                Ok(query) => state
+                    .issues
+                    .iter()
+                    .filter(|issue| query.matches(issue, state))
+                    .cloned()
+                    .collect::<Vec<_>>(),
+                Err(e) => {
+                    return Response::json(400, json!({ "errorMessages": [e], "errors": {} }))
+                }
+            };
+            let (start_at, max_results) = paging(&request.query);
+            Response::json(
+                200,
+                json!({
+                    "expand": "schema,names",
+                    "startAt": start_at,
+                    "maxResults": max_results,
+                    "total": matches.len(),
+                    "issues": page(&matches, start_at, max_results),
+                }),
+            )
+        }
+        _ => Response::not_found("no such endpoint"),
+    }
+}
+
+fn agile(method: &str, path: &[&str], request: &Request, state: &mut State) -> Response {
+    match (method, path) {
+        ("GET", ["board"]) => {
+            let boards = state
+                .boards
+                .iter()
+                .map(|board| state.board(board))
+                .collect::<Vec<_>>();
+            values(&boards, &request.query)
+        }
+        ("GET", ["board", id]) => match state.boards.iter().find(|b| b.0.to_string() == *id) {
+            Some(board) => Response::json(200, state.board(board)),
+            None => Response::not_found("board does not exist"),
+        },
+        ("GET", ["board", id, "issue"]) => {
+            match state.boards.iter().find(|b| b.0.to_string() == *id) {
+                Some(board) => {
+                    let issues = state
+                        .issues
+                        .iter()
+                        .filter(|issue| issue["fields"]["project"]["key"] == *board.2.as_str())
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    let (start_at, max_results) = paging(&request.query);
+                    Response::json(
+                        200,
+                        json!({
+                            "expand": "schema,names",
+                            "startAt": start_at,
+                            "maxResults": max_results,
+                            "total": issues.len(),
+                            "issues": page(&issues, start_at, max_results),
+                        }),
+                    )
+                }
+                None => Response::not_found("board does not exist"),
+            }
+        }
+        ("GET", ["board", id, "sprint"]) => {
+            let sprints = state
+                .sprints
+                .iter()
+                .filter(|sprint| sprint.1.to_string() == *id)
+                .map(|sprint| state.sprint(sprint))
+                .collect::<Vec<_>>();
+            values(&sprints, &request.query)
+        }
+        ("POST", ["sprint", id, "issue"]) => {
+            let sprint = match state.sprints.iter().find(|s| s.0.to_string() == *id) {
+                Some(sprint) => sprint.0,
+                None => return Response::not_found("sprint does not exist"),
+            };
+            let keys = request.body["issues"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            for key in keys.iter().filter_map(Value::as_str) {
+                match state.find(key) {
+                    Some(index) => {
+                        let key = state.issues[index]["key"]
+                            .as_str()
+                            .unwrap_or(key)
+                            .to_owned();
+                        state.sprint_issues.insert(key, sprint);
+                    }
+                    None => return Response::bad_request("issues", "issue does not exist"),
+                }
+            }
+            Response::no_content()
+        }
+        _ => Response::not_found("no such endpoint"),
+    }
+}
+
+fn paging(query: &BTreeMap<String, String>) -> (usize, usize) {
+    let start_at = query
+        .get("startAt")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+    let max_results = query
+        .get("maxResults")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(50);
+    (start_at, max_results)
+}
+
+fn page(values: &[Value], start_at: usize, max_results: usize) -> Vec<Value> {
+    values
+        .iter()
+        .skip(start_at)
+        .take(max_results)
+        .cloned()
+        .collect()
+}
+
+fn values(values: &[Value], query: &BTreeMap<String, String>) -> Response {
+    let (start_at, max_results) = paging(query);
+    Response::json(
+        200,
+        json!({
+            "startAt": start_at,
+            "maxResults": max_results,
+            "isLast": start_at + max_results >= values.len(),
+            "values": page(values, start_at, max_results),
+        }),
+    )
+}
+
+impl State {
+    fn id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn find(&self, key_or_id: &str) -> Option<usize> {
+        let key_or_id = self
+            .keys
+            .iter()
+            .find(|(_, id)| id.to_string() == key_or_id)
+            .map(|(key, _)| key.as_str())
+            .unwrap_or(key_or_id);
+        self.issues
+            .iter()
+            .position(|issue| issue["key"].as_str() == Some(key_or_id))
+    }
+
+    fn status(&self, id: &str) -> Value {
+        let (id, name, category) = STATUSES
+            .iter()
+            .find(|status| status.0 == id)
+            .cloned()
+            .unwrap_or(STATUSES[0]);
+        json!({
+            "self": format!("{}/rest/api/2/status/{}", self.base, id),
+            "description": "",
+            "iconUrl": format!("{}/images/icons/statuses/generic.png", self.base),
+            "id": id,
+            "name": name,
+            "statusCategory": STATUS_CATEGORIES
+                .iter()
+                .find(|status_category| status_category.1 == category)
+                .map(|(id, key, name, color)| json!({
+                    "self": format!("{}/rest/api/2/statuscategory/{}", self.base, id),
+                    "id": id,
+                    "key": key,
+                    "colorName": color,
+                    "name": name,
+                })),
+        })
+    }
+
+    fn board(&self, board: &(u64, String, String)) -> Value {
+        json!({
+            "id": board.0,
+            "self": format!("{}/rest/agile/1.0/board/{}", self.base, board.0),
+            "name": board.1,
+            "type": "scrum",
+        })
+    }
+
+    fn sprint(&self, sprint: &(u64, u64, String, String)) -> Value {
+        json!({
+            "id": sprint.0,
+            "self": format!("{}/rest/agile/1.0/sprint/{}", self.base, sprint.0),
+            "name": sprint.2,
+            "state": sprint.3,
+            "originBoardId": sprint.1,
+        })
+    }
+
+    fn create_issue(&mut self, body: &Value) -> Response {
+        let fields = match body["fields"].as_object() {
+            Some(fields) => fields.clone(),
+            None => return Response::bad_request("fields", "fields are required"),
+        };
+        let project = match self.projects.iter().find(|(id, key, _)| {
+            fields["project"]["key"] == *key.as_str() || fields["project"]["id"] == *id.as_str()
+        }) {
+            Some(project) => project.clone(),
+            None => return Response::bad_request("project", "project is required"),
+        };
+        let issue_type = match ISSUE_TYPES.iter().find(|(id, name, _)| {
+            fields["issuetype"]["id"] == *id || fields["issuetype"]["name"] == *name
+        }) {
+            Some(issue_type) => issue_type,
+            None => return Response::bad_request("issuetype", "issue type is required"),
+        };
+        if fields
+            .get("summary")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .is_empty()
+        {
+            return Response::bad_request("summary", "You must specify a summary of the issue.");
+        }
+
+        self.issue_counter += 1;
+        let id = self.id();
+        let key = format!("{}-{}", project.1, self.issue_counter);
+        let url = format!("{}/rest/api/2/issue/{}", self.base, id);
+        let now = "2019-01-01T00:00:00.000+0000";
+
+        let mut all = Map::new();
+        all.insert("labels".into(), json!([]));
+        all.extend(fields);
+        all.insert(
+            "project".into(),
+            json!({ "id": project.0, "key": project.1, "name": project.2 }),
+        );
+        all.insert(
+            "issuetype".into(),
+            json!({
+                "self": format!("{}/rest/api/2/issuetype/{}", self.base, issue_type.0),
+                "description": "",
+                "iconUrl": format!("{}/images/icons/issuetypes/generic.png", self.base),
+                "id": issue_type.0,
+                "name": issue_type.1,
+                "subtask": issue_type.2,
+            }),
+        );
+        all.insert("status".into(), self.status("1"));
+        all.insert("created".into(), json!(now));
+        all.insert("updated".into(), json!(now));
+
+        // subtasks reference their parent with a subset of its fields, and vice versa
+        let parent = all
+            .get("parent")
+            .and_then(|parent| parent["key"].as_str().or_else(|| parent["id"].as_str()))
+            .and_then(|parent| self.find(parent));
+        if let Some(parent) = parent {
+            let parent_key = self.issues[parent]["key"].as_str().unwrap_or("").to_owned();
+            all.insert("parent".into(), self.linked_issue(&parent_key));
+        }
+
+        self.keys.insert(key.clone(), id);
+        self.issues.push(json!({
+            "self": url,
+            "id": id.to_string(),
+            "key": key,
+            "fields": all,
+        }));
+        if let Some(parent) = parent {
+            let subtask = self.linked_issue(&key);
+            match self.issues[parent]["fields"]["subtasks"].as_array_mut() {
+                Some(subtasks) => subtasks.push(subtask),
+                None => self.issues[parent]["fields"]["subtasks"] = json!([subtask]),
+            }
+        }
+        Response::json(
+            201,
+            json!({ "id": id.to_string(), "key": key, "self": url }),
+        )
+    }
+
+    fn edit_issue(&mut self, key: &str, body: &Value) -> Response {
+        let index = match self.find(key) {
+            Some(index) => index,
+            None => return Response::not_found("Issue Does Not Exist"),
+        };
+        if body["fields"]["summary"] == "" {
+            return Response::bad_request("summary", "You must specify a summary of the issue.");
+        }
+        let fields = &mut self.issues[index]["fields"];
+        if let Some(set) = body["fields"].as_object() {
+            for (name, value) in set {
+                fields[name] = value.clone();
+            }
+        }
+        if let Some(update) = body["update"].as_object() {
+            for (name, operations) in update {
+                for operation in operations.as_array().cloned().unwrap_or_default() {
+                    if let Some(value) = operation.get("set") {
+                        fields[name] = value.clone();
+                    }
+                    if let Some(value) = operation.get("add") {
+                        match fields[name].as_array_mut() {
+                            Some(values) => values.push(value.clone()),
+                            None => fields[name] = json!([value]),
+                        }
+                    }
+                    if let Some(value) = operation.get("remove") {
+                        if let Some(values) = fields[name].as_array_mut() {
+                            values.retain(|v| !same(v, value));
+                        }
+                    }
+                }
+            }
+        }
+        Response::no_content()
+    }
+
+    fn delete_issue(&mut self, key: &str, delete_subtasks: bool) -> Response {
+        let index = match self.find(key) {
+            Some(index) => index,
+            None => return Response::not_found("Issue Does Not Exist"),
+        };
+        let (key, id) = (
+            self.issues[index]["key"].clone(),
+            self.issues[index]["id"].clone(),
+        );
+        let is_subtask = |issue: &Value| {
+            let parent = &issue["fields"]["parent"];
+            (parent["key"] == key || parent["id"] == id) && !parent.is_null()
+        };
+        if self.issues.iter().any(is_subtask) && !delete_subtasks {
+            return Response::json(
+                400,
+                json!({
+                    "errorMessages": [
+                        "The issue has subtasks, set deleteSubtasks=true to delete them with the issue"
+                    ],
+                    "errors": {}
+                }),
+            );
+        }
+        let deleted = self
+            .issues
+            .iter()
+            .filter(|issue| issue["key"] == key || is_subtask(issue))
+            .filter_map(|issue| issue["key"].as_str().map(str::to_owned))
+            .collect::<Vec<_>>();
+        self.issues
+            .retain(|issue| !deleted.iter().any(|key| issue["key"] == *key.as_str()));
+        for issue in &mut self.issues {
+            if let Some(subtasks) = issue["fields"]["subtasks"].as_array_mut() {
+                subtasks
+                    .retain(|subtask| !deleted.iter().any(|key| subtask["key"] == *key.as_str()));
+            }
+        }
+        for key in deleted {
+            self.sprint_issues.remove(&key);
+        }
+        Response::no_content()
+    }
+
+    /// an issue as referenced from another, with a subset of its fields
+    fn linked_issue(&self, key: &str) -> Value {
+        let issue = self.find(key).map(|index| &self.issues[index]);
+        let field = |name: &str| issue.map(|i| i["fields"][name].clone()).unwrap_or_default();
+        json!({
+            "id": issue.map(|i| i["id"].clone()).unwrap_or_default(),
+            "key": key,
+            "self": issue.map(|i| i["self"].clone()).unwrap_or_default(),
+            "fields": {
+                "summary": field("summary"),
+                "status": field("status"),
+                "issuetype": field("issuetype"),
+            },
+        })
+    }
+
+    fn transition(&mut self, key: &str, body: &Value) -> Response {
+        let index = match self.find(key) {
+            Some(index) => index,
+            None => return Response::not_found("Issue Does Not Exist"),
+        };
+        let status = self.issues[index]["fields"]["status"]["id"].clone();
+        let id = body["transition"]["id"].as_str().unwrap_or_default();
+        match TRANSITIONS
+            .iter()
+            .find(|(tid, _, from, _)| *tid == id && status == *from)
+        {
+            Some((_, _, _, to)) => {
+                let status = self.status(to);
+                let fields = &mut self.issues[index]["fields"];
+                fields["status"] = status;
+                if let Some(set) = body["fields"].as_object() {
+                    for (name, value) in set {
+                        fields[name] = value.clone();
+                    }
+                }
+                Response::no_content()
+            }
+            None => Response::bad_request(
+                "transition",
+                "It seems that you have tried to perform a workflow operation that is not valid",
+            ),
+        }
+    }
+
+    /// string values of an issue field for matching jql clauses
+    fn values(&self, issue: &Value, field: &str) -> Vec<String> {
+        let fields = &issue["fields"];
+        let names = |value: &Value, keys: &[&str]| {
+            keys.iter()
+                .filter_map(|k| value[*k].as_str().map(str::to_owned))
+                .collect::<Vec<_>>()
+        };
+        match field {
+            "key" | "issuekey" | "id" => names(issue, &["key", "id"]),
+            "project" => names(&fields["project"], &["key", "name", "id"]),
+            "status" => names(&fields["status"], &["name", "id"]),
+            "statuscategory" => names(&fields["status"]["statusCategory"], &["key"]),
+            "issuetype" | "type" => names(&fields["issuetype"], &["name", "id"]),
+            "assignee" | "reporter" | "creator" => {
+                names(&fields[field], &["name", "accountId", "key"])
+            }
+            "priority" | "resolution" => names(&fields[field], &["name", "id"]),
+            "sprint" => self
+                .sprint_issues
+                .get(issue["key"].as_str().unwrap_or_default())
+                .map(|id| {
+                    let mut values = vec![id.to_string()];
+                    values.extend(
+                        self.sprints
+                            .iter()
+                            .filter(|sprint| sprint.0 == *id)
+                            .map(|sprint| sprint.2.clone()),
+                    );
+                    values
+                })
+                .unwrap_or_default(),
+            other => {
+                let value = fields
+                    .as_object()
+                    .and_then(|fields| {
+                        fields
+                            .iter()
+                            .find(|(name, _)| name.to_lowercase() == other)
+                            .map(|(_, value)| value.clone())
+                    })
+                    .unwrap_or(Value::Null);
+                match value {
+                    Value::Null => vec![],
+                    Value::Array(values) => values.iter().map(scalar).collect(),
+                    value => vec![scalar(&value)],
+                }
+            }
+        }
+    }
+}
+
+/// compares values, matching objects by their name, key or id
+fn same(a: &Value, b: &Value) -> bool {
+    a == b || scalar(a) == scalar(b)
+}
+
+/// a string representation of a value for matching
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Object(object) => ["name", "key", "value", "accountId", "id"]
+            .iter()
+            .find_map(|k| object.get(*k).map(scalar))
+            .unwrap_or_default(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// a parsed jql query, a disjunction of conjunctions of clauses
+#[derive(Debug)]
+struct Query {
+    any: Vec<Vec<Clause>>,
+}
+
+#[derive(Debug)]
+enum Clause {
+    Equals(String, String),
+    NotEquals(String, String),
+    Contains(String, String),
+    In(String, Vec<String>),
+    NotIn(String, Vec<String>),
+    Empty(String),
+    NotEmpty(String),
+}
+
+impl Query {
+    fn matches(&self, issue: &Value, state: &State) -> bool {
+        self.any.is_empty()
+            || self.any.iter().any(|all| {
+                all.iter().all(|clause| {
+                    let values = |field: &str| state.values(issue, field);
+                    let eq = |field: &str, expected: &str| {
+                        values(field)
+                            .iter()
+                            .any(|value| value.eq_ignore_ascii_case(expected))
+                    };
+                    match clause {
+                        Clause::Equals(field, value) => eq(field, value),
+                        Clause::NotEquals(field, value) => !eq(field, value),
+                        Clause::Contains(field, value) => values(field)
+                            .iter()
+                            .any(|v| v.to_lowercase().contains(&value.to_lowercase())),
+                        Clause::In(field, options) => options.iter().any(|o| eq(field, o)),
+                        Clause::NotIn(field, options) => !options.iter().any(|o| eq(field, o)),
+                        Clause::Empty(field) => values(field).is_empty(),
+                        Clause::NotEmpty(field) => !values(field).is_empty(),
+                    }
+                })
+            })
+    }
+}
+
+fn tokenize(jql: &str) -> Result<Vec<String>, String> {
+    let mut tokens = vec![];
+    let mut chars = jql.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' | ')' | ',' | '=' | '~' => {
+                tokens.push(c.to_string());
+                chars.next();
+            }
+            '!' => {
+                chars.next();
+                match chars.next() {
+                    Some('=') => tokens.push("!=".to_owned()),
+                    Some('~') => tokens.push("!~".to_owned()),
+                    _ => return Err("expected an operator after '!'".to_owned()),
+                }
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some(q) if q == c => break,
+                        Some('\\') => value.extend(chars.next()),
+                        Some(other) => value.push(other),
+                        None => return Err("unterminated string".to_owned()),
+                    }
+                }
+                // quoted values are marked so they are never read as keywords
+                tokens.push(format!("\"{}", value));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || "(),=!~\"'".contains(c) {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(word);
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_jql(jql: &str) -> Result<Query, String> {
+    let tokens = tokenize(jql)?;
+    let mut tokens = tokens.iter().map(String::as_str).peekable();
+    let keyword = |token: Option<&&str>, expected: &str| {
+        token.is_some_and(|t| t.eq_ignore_ascii_case(expected))
+    };
+    let value = |token: &str| token.trim_start_matches('"').to_owned();
+
+    let mut any = vec![];
+    let mut all = vec![];
+    while let Some(field) = tokens.next() {
+        if field.eq_ignore_ascii_case("order") {
+            break;
+        }
+        let field = value(field).to_lowercase();
+        let operator = tokens
+            .next()
+            .ok_or_else(|| format!("expected an operator after {}", field))?;
+        let clause = match operator.to_lowercase().as_str() {
+            "=" => Clause::Equals(field, value(tokens.next().unwrap_or_default())),
+            "!=" => Clause::NotEquals(field, value(tokens.next().unwrap_or_default())),
+            "~" => Clause::Contains(field, value(tokens.next().unwrap_or_default())),
+            "is" => {
+                if keyword(tokens.peek(), "not") {
+                    tokens.next();
+                    tokens.next();
+                    Clause::NotEmpty(field)
+                } else {
+                    tokens.next();
+                    Clause::Empty(field)
+                }
+            }
+            op @ "in" | op @ "not" => {
+                if op == "not" && !keyword(tokens.next().as_ref(), "in") {
+                    return Err("expected 'in' after 'not'".to_owned());
+                }
+                if tokens.next() != Some("(") {
+                    return Err("expected '(' after 'in'".to_owned());
+                }
+                let mut options = vec![];
+                for token in tokens.by_ref() {
+                    match token {
+                        ")" => break,
+                        "," => (),
+                        option => options.push(value(option)),
+                    }
+                }
+                if op == "in" {
+                    Clause::In(field, options)
+                } else {
+                    Clause::NotIn(field, options)
+                }
+            }
+            other => return Err(format!("unsupported operator {}", other)),
+        };
+        all.push(clause);
+
+        match tokens.peek() {
+            Some(t) if t.eq_ignore_ascii_case("and") => {
+                tokens.next();
+            }
+            Some(t) if t.eq_ignore_ascii_case("or") => {
+                tokens.next();
+                any.push(all);
+                all = vec![];
+            }
+            Some(t) if t.eq_ignore_ascii_case("order") => break,
+            None => (),
+            Some(t) => return Err(format!("unexpected {}", t)),
+        }
+    }
+    if !all.is_empty() {
+        any.push(all);
+    }
+    Ok(Query { any })
+}
@@ -0,0 +1,300 @@
+//! OAuth 1.0a (RSA-SHA1) support for Jira application links
+//!
+//! Jira application links authenticate consumers with requests signed by an
+//! RSA private key whose public half was registered with the application link.
+//! A one time "dance" exchanges a request token, authorized by a user in their
+//! browser, for a long lived access token which is then used for all requests.
+//!
+//! See the [jira docs](https://developer.atlassian.com/server/jira/platform/oauth/)
+//! for more information
+
+// Third party
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::rand::rand_bytes;
+use openssl::sign::Signer;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use reqwest::header::{HeaderMap, AUTHORIZATION};
+use reqwest::Method;
+use std::io::Read;
+use std::time::{SystemTime, UNIX_EPOCH};
+use url::{form_urlencoded, Url};
+
+// Ours
+use crate::{header_value, Credentials, Error, HttpRequest, Jira, Result};
+
+/// characters outside of the rfc 3986 unreserved set must be percent encoded
+const RESERVED: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// OAuth credentials for an application link consumer
+#[derive(Clone, Debug)]
+pub struct OAuthCredentials {
+    /// the consumer key registered with the application link
+    pub consumer_key: String,
+    /// PEM encoded RSA private key used to sign requests
+    pub private_key: String,
+    /// the access token obtained from `OAuth#access_token`
+    pub token: Option<String>,
+}
+
+impl OAuthCredentials {
+    /// creates a new instance of OAuth credentials for an access token
+    pub fn new<C, K, T>(consumer_key: C, private_key: K, token: T) -> OAuthCredentials
+    where
+        C: Into<String>,
+        K: Into<String>,
+        T: Into<String>,
+    {
+        OAuthCredentials {
+            consumer_key: consumer_key.into(),
+            private_key: private_key.into(),
+            token: Some(token.into()),
+        }
+    }
+
+    /// returns the value of an `Authorization` header signing a request
+    /// for the given method and url. extra oauth protocol parameters,
+    /// ie. `oauth_callback` or `oauth_verifier`, are included in the signature
+    pub fn authorization(
+        &self,
+        method: &Method,
+        url: &str,
+        extra: &[(&str, &str)],
+    ) -> Result<String> {
+        let url = Url::parse(url).map_err(|e| Error::OAuth(e.to_string()))?;
+
+        let mut nonce = [0; 16];
+        rand_bytes(&mut nonce)?;
+        let nonce = nonce
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+            .to_string();
+
+        let mut oauth = vec![
+            ("oauth_consumer_key", self.consumer_key.as_str()),
+            ("oauth_nonce", nonce.as_str()),
+            ("oauth_signature_method", "RSA-SHA1"),
+            ("oauth_timestamp", timestamp.as_str()),
+            ("oauth_version", "1.0"),
+        ];
+        if let Some(ref token) = self.token {
+            oauth.push(("oauth_token", token.as_str()));
+        }
+        oauth.extend_from_slice(extra);
+
+        let signature = self.sign(&signature_base_string(method, &url, &oauth))?;
+        oauth.push(("oauth_signature", signature.as_str()));
+
+        Ok(format!(
+            "OAuth {}",
+            oauth
+                .iter()
+                .map(|(k, v)| format!("{}=\"{}\"", encode(k), encode(v)))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ))
+    }
+
+    fn sign(&self, base: &str) -> Result<String> {
+        let key = PKey::private_key_from_pem(self.private_key.as_bytes())?;
+        let mut signer = Signer::new(MessageDigest::sha1(), &key)?;
+        signer.update(base.as_bytes())?;
+        Ok(base64::encode(&signer.sign_to_vec()?))
+    }
+}
+
+/// credentials of a consumer which has yet to obtain an access token
+fn consumer_credentials(consumer_key: &str, private_key: &str) -> Credentials {
+    Credentials::OAuth(OAuthCredentials {
+        consumer_key: consumer_key.to_owned(),
+        private_key: private_key.to_owned(),
+        token: None,
+    })
+}
+
+/// builds the oauth signature base string for a request
+///
+/// See [rfc 5849 section 3.4.1](https://tools.ietf.org/html/rfc5849#section-3.4.1)
+pub fn signature_base_string(method: &Method, url: &Url, oauth: &[(&str, &str)]) -> String {
+    let mut params = url
+        .query_pairs()
+        .map(|(k, v)| (encode(&k), encode(&v)))
+        .chain(oauth.iter().map(|(k, v)| (encode(k), encode(v))))
+        .collect::<Vec<(String, String)>>();
+    params.sort();
+    let params = params
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<String>>()
+        .join("&");
+
+    let mut base_url = url.clone();
+    base_url.set_query(None);
+    base_url.set_fragment(None);
+
+    format!(
+        "{}&{}&{}",
+        method.as_str(),
+        encode(base_url.as_str()),
+        encode(&params)
+    )
+}
+
+fn encode(value: &str) -> String {
+    utf8_percent_encode(value, RESERVED).to_string()
+}
+
+/// a token and secret pair issued during the oauth dance
+#[derive(Clone, Debug)]
+pub struct Token {
+    pub token: String,
+    pub secret: String,
+}
+
+/// helpers for the oauth request-token, authorize and access-token dance
+#[derive(Debug)]
+pub struct OAuth {
+    jira: Jira,
+    consumer_key: String,
+    private_key: String,
+}
+
+impl OAuth {
+    /// creates a new instance for a jira host and application link consumer.
+    /// see `Jira#oauth` for sending token requests with a configured client
+    pub fn new<H, C, K>(host: H, consumer_key: C, private_key: K) -> Result<OAuth>
+    where
+        H: Into<String>,
+        C: Into<String>,
+        K: Into<String>,
+    {
+        let consumer_key = consumer_key.into();
+        let private_key = private_key.into();
+        let jira = Jira::new(host, consumer_credentials(&consumer_key, &private_key))?;
+        Ok(OAuth {
+            jira,
+            consumer_key,
+            private_key,
+        })
+    }
+
+    pub(crate) fn from_jira<C, K>(jira: &Jira, consumer_key: C, private_key: K) -> OAuth
+    where
+        C: Into<String>,
+        K: Into<String>,
+    {
+        let consumer_key = consumer_key.into();
+        let private_key = private_key.into();
+        OAuth {
+            jira: Jira {
+                credentials: consumer_credentials(&consumer_key, &private_key),
+                ..jira.clone()
+            },
+            consumer_key,
+            private_key,
+        }
+    }
+
+    /// obtains a temporary request token. the callback defaults to `oob`,
+    /// in which case jira displays a verification code to the user
+    pub fn request_token(&self, callback: Option<&str>) -> Result<Token> {
+        self.token_request(
+            "request-token",
+            None,
+            &[("oauth_callback", callback.unwrap_or("oob"))],
+        )
+    }
+
+    /// returns the url a user must visit to authorize a request token
+    pub fn authorize_url(&self, request_token: &Token) -> String {
+        format!(
+            "{}/plugins/servlet/oauth/authorize?oauth_token={}",
+            self.jira.host,
+            encode(&request_token.token)
+        )
+    }
+
+    /// exchanges an authorized request token and verifier for an access token
+    pub fn access_token(&self, request_token: &Token, verifier: &str) -> Result<Token> {
+        self.token_request(
+            "access-token",
+            Some(request_token.token.clone()),
+            &[("oauth_verifier", verifier)],
+        )
+    }
+
+    /// returns credentials which sign requests with an access token
+    pub fn credentials(&self, access_token: &Token) -> Credentials {
+        Credentials::OAuth(OAuthCredentials::new(
+            self.consumer_key.clone(),
+            self.private_key.clone(),
+            access_token.token.clone(),
+        ))
+    }
+
+    fn token_request(
+        &self,
+        endpoint: &str,
+        token: Option<String>,
+        extra: &[(&str, &str)],
+    ) -> Result<Token> {
+        let url = format!("{}/plugins/servlet/oauth/{}", self.jira.host, endpoint);
+        debug!("url -> {:?}", url);
+        let credentials = OAuthCredentials {
+            consumer_key: self.consumer_key.clone(),
+            private_key: self.private_key.clone(),
+            token,
+        };
+        // each attempt is signed anew, as jira rejects a reused nonce
+        let mut res = self
+            .jira
+            .retrying(self.jira.retry.retries(&Method::POST), || {
+                let mut headers = HeaderMap::new();
+                headers.insert(
+                    AUTHORIZATION,
+                    header_value(credentials.authorization(&Method::POST, &url, extra)?)?,
+                );
+                Ok(HttpRequest {
+                    method: Method::POST,
+                    url: url.clone(),
+                    headers,
+                    body: None,
+                })
+            })?;
+        let mut body = String::new();
+        res.body.read_to_string(&mut body)?;
+        debug!("status {:?} body '{:?}'", res.status, body);
+        if !res.status.is_success() {
+            return Err(Error::OAuth(format!(
+                "{} responded with {}: {}",
+                endpoint, res.status, body
+            )));
+        }
+
+        let mut token = None;
+        let mut secret = None;
+        for (k, v) in form_urlencoded::parse(body.as_bytes()) {
+            match k.as_ref() {
+                "oauth_token" => token = Some(v.into_owned()),
+                "oauth_token_secret" => secret = Some(v.into_owned()),
+                _ => (),
+            }
+        }
+        match (token, secret) {
+            (Some(token), Some(secret)) => Ok(Token { token, secret }),
+            _ => Err(Error::OAuth(format!(
+                "{} response did not include a token: {}",
+                endpoint, body
+            ))),
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Interfaces for accessing and managing links from issues to remote resources
+
+// Ours
+use crate::{EmptyResponse, Jira, Result};
+
+/// issue remote links interface
+#[derive(Debug)]
+pub struct RemoteLinks {
+    jira: Jira,
+    key: String,
+}
+
+/// a link from an issue to a resource outside of jira
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteLink {
+    pub id: u64,
+    #[serde(rename = "self")]
+    pub self_link: String,
+    pub global_id: Option<String>,
+    pub application: Option<Application>,
+    pub relationship: Option<String>,
+    pub object: RemoteObject,
+}
+
+/// the application hosting a remote resource
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Application {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub app_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// a remote resource
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RemoteObject {
+    pub url: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<RemoteIcon>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<RemoteStatus>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RemoteIcon {
+    #[serde(rename = "url16x16", skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// the state of a remote resource, ie. whether a pull request was merged
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RemoteStatus {
+    pub resolved: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<RemoteIcon>,
+}
+
+/// a new or updated remote link
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRemoteLink {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application: Option<Application>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relationship: Option<String>,
+    pub object: RemoteObject,
+}
+
+impl CreateRemoteLink {
+    pub fn new<U, T>(url: U, title: T) -> CreateRemoteLink
+    where
+        U: Into<String>,
+        T: Into<String>,
+    {
+        CreateRemoteLink {
+            global_id: None,
+            application: None,
+            relationship: None,
+            object: RemoteObject {
+                url: url.into(),
+                title: title.into(),
+                summary: None,
+                icon: None,
+                status: None,
+            },
+        }
+    }
+
+    /// identifies the remote resource. creating a link with the global id
+    /// of an existing link on the issue updates that link instead
+    pub fn global_id<G>(mut self, global_id: G) -> CreateRemoteLink
+    where
+        G: Into<String>,
+    {
+        self.global_id = Some(global_id.into());
+        self
+    }
+
+    /// the type and name of the application hosting the resource
+    pub fn application<T, N>(mut self, app_type: T, name: N) -> CreateRemoteLink
+    where
+        T: Into<String>,
+        N: Into<String>,
+    {
+        self.application = Some(Application {
+            app_type: Some(app_type.into()),
+            name: Some(name.into()),
+        });
+        self
+    }
+
+    /// describes the link, ie. "causes" or "mentioned in"
+    pub fn relationship<R>(mut self, relationship: R) -> CreateRemoteLink
+    where
+        R: Into<String>,
+    {
+        self.relationship = Some(relationship.into());
+        self
+    }
+
+    pub fn summary<S>(mut self, summary: S) -> CreateRemoteLink
+    where
+        S: Into<String>,
+    {
+        self.object.summary = Some(summary.into());
+        self
+    }
+
+    pub fn icon<U, T>(mut self, url: U, title: T) -> CreateRemoteLink
+    where
+        U: Into<String>,
+        T: Into<String>,
+    {
+        self.object.icon = Some(RemoteIcon {
+            url: Some(url.into()),
+            title: Some(title.into()),
+        });
+        self
+    }
+
+    /// marks the remote resource as resolved, which jira renders struck through
+    pub fn resolved(mut self, resolved: bool) -> CreateRemoteLink {
+        self.object.status = Some(RemoteStatus {
+            resolved,
+            icon: None,
+        });
+        self
+    }
+}
+
+/// the identity of a created or updated remote link
+#[derive(Deserialize, Debug)]
+pub struct RemoteLinkCreated {
+    pub id: u64,
+    #[serde(rename = "self")]
+    pub self_link: String,
+}
+
+impl RemoteLinks {
+    pub fn new<K>(jira: &Jira, key: K) -> RemoteLinks
+    where
+        K: Into<String>,
+    {
+        RemoteLinks {
+            jira: jira.clone(),
+            key: key.into(),
+        }
+    }
+
+    /// returns all remote links of the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getRemoteIssueLinks
+    pub fn list(&self) -> Result<Vec<RemoteLink>> {
+        self.jira
+            .get("api", &format!("/issue/{}/remotelink", self.key))
+    }
+
+    /// returns a remote link of the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getRemoteIssueLinkById
+    pub fn get(&self, id: u64) -> Result<RemoteLink> {
+        self.jira
+            .get("api", &format!("/issue/{}/remotelink/{}", self.key, id))
+    }
+
+    /// returns the remote link of the issue with a global id
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getRemoteIssueLinks
+    pub fn find<G>(&self, global_id: G) -> Result<RemoteLink>
+    where
+        G: Into<String>,
+    {
+        self.jira
+            .get("api", &global_id_path(&self.key, &global_id.into()))
+    }
+
+    /// creates a remote link, or updates the existing link with the same global id
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-createOrUpdateRemoteIssueLink
+    pub fn create(&self, link: CreateRemoteLink) -> Result<RemoteLinkCreated> {
+        self.jira
+            .post("api", &format!("/issue/{}/remotelink", self.key), link)
+    }
+
+    /// replaces a remote link of the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-updateRemoteIssueLink
+    pub fn update(&self, id: u64, link: CreateRemoteLink) -> Result<()> {
+        self.jira.put::<EmptyResponse, _>(
+            "api",
+            &format!("/issue/{}/remotelink/{}", self.key, id),
+            link,
+        )?;
+        Ok(())
+    }
+
+    /// removes a remote link of the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-deleteRemoteIssueLinkById
+    pub fn delete(&self, id: u64) -> Result<()> {
+        self.jira
+            .delete::<EmptyResponse>("api", &format!("/issue/{}/remotelink/{}", self.key, id))?;
+        Ok(())
+    }
+
+    /// removes the remote link of the issue with a global id
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-deleteRemoteIssueLinkByGlobalId
+    pub fn delete_global<G>(&self, global_id: G) -> Result<()>
+    where
+        G: Into<String>,
+    {
+        self.jira
+            .delete::<EmptyResponse>("api", &global_id_path(&self.key, &global_id.into()))?;
+        Ok(())
+    }
+}
+
+pub(crate) fn global_id_path(key: &str, global_id: &str) -> String {
+    format!(
+        "/issue/{}/remotelink?{}",
+        key,
+        url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("globalId", global_id)
+            .finish()
+    )
+}
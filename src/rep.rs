@@ -2,20 +2,36 @@
 
 use serde::Deserialize;
 use serde::Serialize;
-use serde_json;
 use std::collections::BTreeMap;
 
 // Ours
-use crate::{Jira, Result};
+use crate::worklogs::WorklogResults;
+use crate::{Account, Jira, Result};
 
 /// represents an general jira error response
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 pub struct Errors {
-    #[serde(rename = "errorMessages")]
+    #[serde(rename = "errorMessages", default)]
     pub error_messages: Vec<String>,
+    #[serde(default)]
     pub errors: BTreeMap<String, String>,
 }
 
+impl Errors {
+    /// parses an error response body. bodies which aren't jira's json error format,
+    /// like html error pages from a proxy, are kept as the only error message
+    pub(crate) fn from_body(body: &str) -> Errors {
+        serde_json::from_str::<Errors>(body).unwrap_or_else(|_| Errors {
+            error_messages: if body.trim().is_empty() {
+                vec![]
+            } else {
+                vec![body.to_owned()]
+            },
+            errors: BTreeMap::new(),
+        })
+    }
+}
+
 /// represents a single jira issue
 #[derive(Deserialize, Debug, Clone)]
 pub struct Issue {
@@ -28,13 +44,16 @@ pub struct Issue {
 }
 
 impl Issue {
-    /// resolves a typed field from an issues lists of arbitrary fields
+    /// resolves a typed field from an issues lists of arbitrary fields. returns
+    /// None when the field is missing or null, and an error when it can't be
+    /// deserialized as `F`
     pub fn field<F>(&self, name: &str) -> Option<Result<F>>
     where
         for<'de> F: Deserialize<'de>,
     {
         self.fields
             .get(name)
+            .filter(|value| !value.is_null())
             .map(|value| Ok(serde_json::value::from_value::<F>(value.clone())?))
     }
 
@@ -47,103 +66,202 @@ impl Issue {
     }
 
     /// user assigned to issue
-    pub fn assignee(&self) -> Option<User> {
-        self.user_field("assignee").and_then(|value| value.ok())
+    pub fn assignee(&self) -> Option<Result<User>> {
+        self.user_field("assignee")
     }
 
     /// user that created the issue
-    pub fn creator(&self) -> Option<User> {
-        self.user_field("creator").and_then(|value| value.ok())
+    pub fn creator(&self) -> Option<Result<User>> {
+        self.user_field("creator")
     }
 
     /// user that reported the issue
-    pub fn reporter(&self) -> Option<User> {
-        self.user_field("reporter").and_then(|value| value.ok())
+    pub fn reporter(&self) -> Option<Result<User>> {
+        self.user_field("reporter")
     }
 
     /// the current status of the issue
-    pub fn status(&self) -> Option<Status> {
-        self.field::<Status>("status").and_then(|value| value.ok())
+    pub fn status(&self) -> Option<Result<Status>> {
+        self.field::<Status>("status")
+    }
+
+    /// the category of the issue's current status, ie. "done"
+    pub fn status_category(&self) -> Option<Result<StatusCategory>> {
+        self.status().and_then(|status| match status {
+            Ok(status) => status.status_category.map(Ok),
+            Err(e) => Some(Err(e)),
+        })
     }
 
     /// brief summary of the issue
-    pub fn summary(&self) -> Option<String> {
-        self.string_field("summary").and_then(|value| value.ok())
+    pub fn summary(&self) -> Option<Result<String>> {
+        self.string_field("summary")
     }
 
     /// description of the issue
-    pub fn description(&self) -> Option<String> {
+    pub fn description(&self) -> Option<Result<String>> {
         self.string_field("description")
-            .and_then(|value| value.ok())
+    }
+
+    /// the environment the issue occurs in
+    pub fn environment(&self) -> Option<Result<String>> {
+        self.string_field("environment")
     }
 
     /// updated timestamp
-    pub fn updated(&self) -> Option<String> {
-        self.string_field("updated").and_then(|value| value.ok())
+    pub fn updated(&self) -> Option<Result<String>> {
+        self.string_field("updated")
     }
 
     /// created timestamp
-    pub fn created(&self) -> Option<String> {
-        self.string_field("created").and_then(|value| value.ok())
+    pub fn created(&self) -> Option<Result<String>> {
+        self.string_field("created")
+    }
+
+    /// timestamp of when the current user last viewed the issue
+    pub fn last_viewed(&self) -> Option<Result<String>> {
+        self.string_field("lastViewed")
     }
 
-    pub fn resolution_date(&self) -> Option<String> {
+    pub fn resolution_date(&self) -> Option<Result<String>> {
         self.string_field("resolutiondate")
-            .and_then(|value| value.ok())
+    }
+
+    /// due date, ie. "2019-01-31"
+    pub fn due_date(&self) -> Option<Result<String>> {
+        self.string_field("duedate")
     }
 
     /// an issue type
-    pub fn issue_type(&self) -> Option<IssueType> {
+    pub fn issue_type(&self) -> Option<Result<IssueType>> {
         self.field::<IssueType>("issuetype")
-            .and_then(|value| value.ok())
     }
 
     /// labels associated with the issue
-    pub fn labels(&self) -> Vec<String> {
+    pub fn labels(&self) -> Option<Result<Vec<String>>> {
         self.field::<Vec<String>>("labels")
-            .and_then(|value| value.ok())
-            .unwrap_or_default()
+    }
+
+    /// components of the issue's project the issue belongs to
+    pub fn components(&self) -> Option<Result<Vec<ProjectComponent>>> {
+        self.field::<Vec<ProjectComponent>>("components")
     }
 
     /// list of versions associated with the issue
-    pub fn fix_versions(&self) -> Vec<Version> {
+    pub fn fix_versions(&self) -> Option<Result<Vec<Version>>> {
         self.field::<Vec<Version>>("fixVersions")
-            .and_then(|value| value.ok())
-            .unwrap_or_default()
+    }
+
+    /// list of versions affected by the issue
+    pub fn versions(&self) -> Option<Result<Vec<Version>>> {
+        self.field::<Vec<Version>>("versions")
     }
 
     /// priority of the issue
-    pub fn priority(&self) -> Option<Priority> {
+    pub fn priority(&self) -> Option<Result<Priority>> {
         self.field::<Priority>("priority")
-            .and_then(|value| value.ok())
     }
 
     /// links to other issues
     pub fn links(&self) -> Option<Result<Vec<IssueLink>>> {
-        self.field::<Vec<IssueLink>>("issuelinks") //.and_then(|value| value.ok()).unwrap_or(vec![])
+        self.field::<Vec<IssueLink>>("issuelinks")
+    }
+
+    /// the parent of a subtask, with a subset of its fields
+    pub fn parent(&self) -> Option<Result<Issue>> {
+        self.field::<Issue>("parent")
     }
 
-    pub fn project(&self) -> Option<Project> {
+    /// subtasks of the issue, with a subset of their fields
+    pub fn subtasks(&self) -> Option<Result<Vec<Issue>>> {
+        self.field::<Vec<Issue>>("subtasks")
+    }
+
+    pub fn project(&self) -> Option<Result<Project>> {
         self.field::<Project>("project")
-            .and_then(|value| value.ok())
     }
 
-    pub fn resolution(&self) -> Option<Resolution> {
+    pub fn resolution(&self) -> Option<Result<Resolution>> {
         self.field::<Resolution>("resolution")
-            .and_then(|value| value.ok())
     }
 
-    pub fn attachment(&self) -> Vec<Attachment> {
+    /// the security level restricting who may see the issue
+    pub fn security_level(&self) -> Option<Result<SecurityLevel>> {
+        self.field::<SecurityLevel>("security")
+    }
+
+    pub fn attachment(&self) -> Option<Result<Vec<Attachment>>> {
         self.field::<Vec<Attachment>>("attachment")
-            .and_then(|value| value.ok())
-            .unwrap_or_default()
     }
 
-    pub fn comment(&self) -> Vec<Comment> {
+    /// estimates of and time spent on the issue
+    pub fn timetracking(&self) -> Option<Result<TimeTracking>> {
+        self.field::<TimeTracking>("timetracking")
+    }
+
+    /// the original estimate of the issue, in seconds
+    pub fn time_original_estimate(&self) -> Option<Result<u64>> {
+        self.field::<u64>("timeoriginalestimate")
+    }
+
+    /// the remaining estimate of the issue, in seconds
+    pub fn time_estimate(&self) -> Option<Result<u64>> {
+        self.field::<u64>("timeestimate")
+    }
+
+    /// the time spent on the issue, in seconds
+    pub fn time_spent(&self) -> Option<Result<u64>> {
+        self.field::<u64>("timespent")
+    }
+
+    /// the original estimate of the issue and its subtasks, in seconds
+    pub fn aggregate_time_original_estimate(&self) -> Option<Result<u64>> {
+        self.field::<u64>("aggregatetimeoriginalestimate")
+    }
+
+    /// the remaining estimate of the issue and its subtasks, in seconds
+    pub fn aggregate_time_estimate(&self) -> Option<Result<u64>> {
+        self.field::<u64>("aggregatetimeestimate")
+    }
+
+    /// the time spent on the issue and its subtasks, in seconds
+    pub fn aggregate_time_spent(&self) -> Option<Result<u64>> {
+        self.field::<u64>("aggregatetimespent")
+    }
+
+    /// the time spent as a percentage of the original estimate
+    pub fn work_ratio(&self) -> Option<Result<i64>> {
+        self.field::<i64>("workratio")
+    }
+
+    /// progress of the issue, in seconds spent of the total estimate
+    pub fn progress(&self) -> Option<Result<Progress>> {
+        self.field::<Progress>("progress")
+    }
+
+    /// progress of the issue and its subtasks
+    pub fn aggregate_progress(&self) -> Option<Result<Progress>> {
+        self.field::<Progress>("aggregateprogress")
+    }
+
+    /// votes for the issue
+    pub fn votes(&self) -> Option<Result<Votes>> {
+        self.field::<Votes>("votes")
+    }
+
+    /// users watching the issue
+    pub fn watches(&self) -> Option<Result<Watches>> {
+        self.field::<Watches>("watches")
+    }
+
+    pub fn comment(&self) -> Option<Result<Vec<Comment>>> {
         self.field::<Comments>("comment")
-            .and_then(|value| value.ok())
-            .map(|value| value.comments)
-            .unwrap_or_default()
+            .map(|value| value.map(|value| value.comments))
+    }
+
+    /// the first page of the issue's worklogs
+    pub fn worklog(&self) -> Option<Result<WorklogResults>> {
+        self.field::<WorklogResults>("worklog")
     }
 
     pub fn permalink(&self, jira: &Jira) -> String {
@@ -151,6 +269,68 @@ impl Issue {
     }
 }
 
+/// a component of a project
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProjectComponent {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(rename = "self")]
+    pub self_link: String,
+}
+
+/// restricts who may see an issue
+#[derive(Deserialize, Debug, Clone)]
+pub struct SecurityLevel {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(rename = "self")]
+    pub self_link: String,
+}
+
+/// progress of an issue, as time spent of the total estimate, in seconds
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct Progress {
+    pub progress: u64,
+    pub total: u64,
+    /// only present when there is an estimate
+    pub percent: Option<u64>,
+}
+
+/// a summary of the votes for an issue. see `votes::Votes` for the voters
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Votes {
+    #[serde(rename = "self")]
+    pub self_link: String,
+    pub votes: u64,
+    pub has_voted: bool,
+}
+
+/// a summary of the users watching an issue. see `watchers::Watchers` for the watchers
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Watches {
+    #[serde(rename = "self")]
+    pub self_link: String,
+    pub watch_count: u64,
+    pub is_watching: bool,
+}
+
+/// estimates of and time spent on an issue, in jira's duration format, ie. "3h 20m",
+/// and in seconds. each is absent when time tracking is disabled or nothing is recorded
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeTracking {
+    pub original_estimate: Option<String>,
+    pub remaining_estimate: Option<String>,
+    pub time_spent: Option<String>,
+    pub original_estimate_seconds: Option<u64>,
+    pub remaining_estimate_seconds: Option<u64>,
+    pub time_spent_seconds: Option<u64>,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct Attachment {
     pub id: String,
@@ -182,16 +362,44 @@ pub struct Comment {
     pub created: String,
     pub updated: String,
     pub body: String,
+    /// the body rendered as html, when requested with `expand=renderedBody`
+    #[serde(rename = "renderedBody")]
+    pub rendered_body: Option<String>,
     pub visibility: Option<Visibility>,
 }
 
-#[derive(Deserialize, Debug)]
+/// restricts who may see a comment
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Visibility {
     #[serde(rename = "type")]
     pub visibility_type: String,
     pub value: String,
 }
 
+impl Visibility {
+    /// visible only to members of a project role
+    pub fn role<R>(role: R) -> Visibility
+    where
+        R: Into<String>,
+    {
+        Visibility {
+            visibility_type: "role".to_owned(),
+            value: role.into(),
+        }
+    }
+
+    /// visible only to members of a group
+    pub fn group<G>(group: G) -> Visibility
+    where
+        G: Into<String>,
+    {
+        Visibility {
+            visibility_type: "group".to_owned(),
+            value: group.into(),
+        }
+    }
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct Changelog {
     pub histories: Vec<History>,
@@ -257,23 +465,43 @@ pub struct Version {
     pub self_link: String,
 }
 
+/// a user of Jira Server, identified by `name`, or of Jira Cloud, identified by `account_id`
 #[derive(Deserialize, Debug, Clone)]
 pub struct User {
     pub active: bool,
-    #[serde(rename = "avatarUrls")]
+    #[serde(rename = "avatarUrls", default)]
     pub avatar_urls: BTreeMap<String, String>,
     #[serde(rename = "displayName")]
     pub display_name: String,
+    /// hidden by Jira Cloud unless permitted by the user's profile visibility
     #[serde(rename = "emailAddress")]
-    pub email_address: String,
+    pub email_address: Option<String>,
     pub key: Option<String>,
-    pub name: String,
+    /// only provided by Jira Server
+    pub name: Option<String>,
+    /// only provided by Jira Cloud
+    #[serde(rename = "accountId")]
+    pub account_id: Option<String>,
+    /// the kind of Jira Cloud account, ie. "atlassian", "app" or "customer"
+    #[serde(rename = "accountType")]
+    pub account_type: Option<String>,
     #[serde(rename = "self")]
     pub self_link: String,
     #[serde(rename = "timeZone")]
     pub timezone: Option<String>,
 }
 
+impl User {
+    /// identifies the user in requests, by account id on Jira Cloud or name on Jira Server
+    pub fn account(&self) -> Option<Account> {
+        match (&self.account_id, &self.name) {
+            (Some(id), _) => Some(Account::AccountId(id.clone())),
+            (None, Some(name)) => Some(Account::Name(name.clone())),
+            (None, None) => None,
+        }
+    }
+}
+
 #[derive(Deserialize, Debug)]
 pub struct Status {
     pub description: String,
@@ -283,10 +511,26 @@ pub struct Status {
     pub name: String,
     #[serde(rename = "self")]
     pub self_link: String,
+    #[serde(rename = "statusCategory")]
+    pub status_category: Option<StatusCategory>,
+}
+
+/// groups statuses by how far along their workflow issues are
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusCategory {
+    pub id: u64,
+    /// "new", "indeterminate" or "done"
+    pub key: String,
+    pub name: String,
+    pub color_name: String,
+    #[serde(rename = "self")]
+    pub self_link: String,
 }
 
 #[derive(Deserialize, Debug)]
 pub struct Priority {
+    #[serde(rename = "iconUrl")]
     pub icon_url: String,
     pub id: String,
     pub name: String,
@@ -397,7 +641,14 @@ impl TransitionTriggerOptionsBuilder {
     where
         R: Into<String>,
     {
-        self.field("resolution", Resolution { name: name.into() });
+        self.field(
+            "resolution",
+            Resolution {
+                id: None,
+                name: name.into(),
+                description: None,
+            },
+        );
         self
     }
 
@@ -411,7 +662,11 @@ impl TransitionTriggerOptionsBuilder {
 
 #[derive(Serialize, Debug, Deserialize)]
 pub struct Resolution {
-    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
 #[derive(Serialize, Clone, Debug)]
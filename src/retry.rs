@@ -0,0 +1,186 @@
+//! Policies for retrying requests which fail transiently
+
+// Third party
+use reqwest::{Method, StatusCode};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime};
+
+/// describes which requests are retried, how many times,
+/// and how long to wait between attempts
+///
+/// Waits grow exponentially from `initial_backoff` up to `max_backoff`.
+/// A `Retry-After` header sent with a retried response takes precedence, unless
+/// it asks for a longer wait than `max_backoff`, in which case the response is
+/// returned without retrying. Session logins are retried whatever their method,
+/// as repeating one only issues another session
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    statuses: Vec<StatusCode>,
+    methods: Vec<Method>,
+}
+
+impl Default for RetryPolicy {
+    /// retries idempotent requests up to 3 times when rate limited or
+    /// when a gateway or service is unavailable
+    fn default() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            methods: vec![
+                Method::GET,
+                Method::HEAD,
+                Method::OPTIONS,
+                Method::PUT,
+                Method::DELETE,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// return a new instance of a builder for a policy, starting from the defaults
+    pub fn builder() -> RetryPolicyBuilder {
+        RetryPolicyBuilder::new()
+    }
+
+    /// a policy which never retries requests
+    pub fn none() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// whether requests with a method may be retried
+    pub(crate) fn retries(&self, method: &Method) -> bool {
+        self.methods.contains(method)
+    }
+
+    /// returns how long to wait before retrying a request which has been
+    /// attempted `attempt` times, or None if it should not be retried.
+    /// `status` is None when no response was received
+    pub(crate) fn delay(
+        &self,
+        attempt: u32,
+        status: Option<StatusCode>,
+        retry_after: Option<&str>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        match status {
+            Some(status) if !self.statuses.contains(&status) => None,
+            _ => match retry_after.and_then(parse_retry_after) {
+                Some(delay) if delay > self.max_backoff => None,
+                Some(delay) => Some(delay),
+                None => Some(self.backoff(attempt)),
+            },
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .checked_mul(2u32.saturating_pow(attempt - 1))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+        if self.jitter {
+            // wait somewhere between half and all of the backoff so that
+            // concurrent clients don't retry in lockstep
+            let half = backoff / 2;
+            let random = RandomState::new().build_hasher().finish();
+            half + Duration::from_nanos(random % (half.as_nanos() as u64 + 1))
+        } else {
+            backoff
+        }
+    }
+}
+
+/// parses a `Retry-After` header, either delay seconds or an http date
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    value
+        .trim()
+        .parse::<u64>()
+        .map(Duration::from_secs)
+        .ok()
+        .or_else(|| {
+            httpdate::parse_http_date(value.trim())
+                .ok()
+                .map(|date| date.duration_since(SystemTime::now()).unwrap_or_default())
+        })
+}
+
+/// a builder interface for retry policies
+/// Typically this is initialized with RetryPolicy::builder()
+#[derive(Debug)]
+pub struct RetryPolicyBuilder {
+    policy: RetryPolicy,
+}
+
+impl Default for RetryPolicyBuilder {
+    fn default() -> RetryPolicyBuilder {
+        RetryPolicyBuilder::new()
+    }
+}
+
+impl RetryPolicyBuilder {
+    pub fn new() -> RetryPolicyBuilder {
+        RetryPolicyBuilder {
+            policy: RetryPolicy::default(),
+        }
+    }
+
+    /// total number of attempts, including the first, made for a request
+    pub fn max_attempts(&mut self, n: u32) -> &mut RetryPolicyBuilder {
+        self.policy.max_attempts = n.max(1);
+        self
+    }
+
+    /// wait before the first retry, doubled for each subsequent retry
+    pub fn initial_backoff(&mut self, d: Duration) -> &mut RetryPolicyBuilder {
+        self.policy.initial_backoff = d;
+        self
+    }
+
+    /// upper bound on the wait between retries
+    pub fn max_backoff(&mut self, d: Duration) -> &mut RetryPolicyBuilder {
+        self.policy.max_backoff = d;
+        self
+    }
+
+    /// randomizes waits between half and all of the backoff
+    pub fn jitter(&mut self, j: bool) -> &mut RetryPolicyBuilder {
+        self.policy.jitter = j;
+        self
+    }
+
+    /// response statuses which are retried
+    pub fn statuses(&mut self, s: Vec<StatusCode>) -> &mut RetryPolicyBuilder {
+        self.policy.statuses = s;
+        self
+    }
+
+    /// request methods which are retried. non-idempotent methods like POST
+    /// are excluded by default
+    pub fn methods(&mut self, m: Vec<Method>) -> &mut RetryPolicyBuilder {
+        self.policy.methods = m;
+        self
+    }
+
+    pub fn build(&self) -> RetryPolicy {
+        self.policy.clone()
+    }
+}
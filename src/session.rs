@@ -0,0 +1,133 @@
+//! Cookie based session authentication
+//!
+//! See the [jira docs](https://docs.atlassian.com/software/jira/docs/api/REST/latest/#auth/1/session)
+//! for more information
+
+// Third party
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, COOKIE};
+use reqwest::{Method, StatusCode};
+use std::sync::{Arc, Mutex};
+
+// Ours
+use crate::{Error, Errors, HttpRequest, Result, Transport};
+
+/// the header in which jira explains why it refused a login
+const DENIED_REASON: &str = "x-authentication-denied-reason";
+
+#[derive(Serialize, Debug)]
+struct Login<'a> {
+    username: &'a str,
+    password: &'a str,
+}
+
+#[derive(Deserialize, Debug)]
+struct LoginResponse {
+    session: SessionInfo,
+}
+
+/// the session cookie issued by jira on login
+#[derive(Deserialize, Debug, Clone)]
+pub struct SessionInfo {
+    pub name: String,
+    pub value: String,
+}
+
+/// holds the session cookie shared between clones of a `Jira` client
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Session {
+    cookie: Arc<Mutex<Option<SessionInfo>>>,
+}
+
+impl Session {
+    /// returns the current session cookie as a `Cookie` header value
+    pub fn cookie(&self) -> Option<String> {
+        self.cookie
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|session| format!("{}={}", session.name, session.value))
+    }
+
+    /// forgets the current session cookie
+    pub fn invalidate(&self) {
+        self.cookie.lock().unwrap().take();
+    }
+
+    /// a login request for a jira host
+    pub fn request(host: &str, user: &str, pass: &str) -> Result<HttpRequest> {
+        let url = Session::url(host);
+        debug!("url -> {:?}", url);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        Ok(HttpRequest {
+            method: Method::POST,
+            url,
+            headers,
+            body: Some(Session::credentials(user, pass)?.into_bytes()),
+        })
+    }
+
+    /// logs out of the current session, if any
+    pub fn logout(&self, transport: &dyn Transport, host: &str) -> Result<()> {
+        if let Some(cookie) = self.cookie() {
+            let url = Session::url(host);
+            debug!("url -> {:?}", url);
+
+            let mut headers = HeaderMap::new();
+            if let Ok(cookie) = HeaderValue::from_str(&cookie) {
+                headers.insert(COOKIE, cookie);
+            }
+            transport.send(HttpRequest {
+                method: Method::DELETE,
+                url,
+                headers,
+                body: None,
+            })?;
+            self.invalidate();
+        }
+        Ok(())
+    }
+
+    /// the session resource of a jira host
+    pub fn url(host: &str) -> String {
+        format!("{}/rest/auth/1/session", host)
+    }
+
+    /// the json body of a login request
+    pub fn credentials(user: &str, pass: &str) -> Result<String> {
+        Ok(serde_json::to_string(&Login {
+            username: user,
+            password: pass,
+        })?)
+    }
+
+    /// stores the session cookie from a login response
+    pub fn store(&self, status: StatusCode, headers: &HeaderMap, body: &str) -> Result<String> {
+        match status {
+            StatusCode::UNAUTHORIZED => Err(Error::Unauthorized),
+            StatusCode::FORBIDDEN => Err(Error::AuthenticationDenied {
+                reason: headers
+                    .get(DENIED_REASON)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_owned),
+                errors: Errors::from_body(body),
+            }),
+            client_err if client_err.is_client_error() => Err(Error::Fault {
+                code: status,
+                errors: Errors::from_body(body),
+            }),
+            server_err if server_err.is_server_error() => Err(Error::Server {
+                code: status,
+                headers: headers.clone(),
+                body: body.to_owned(),
+            }),
+            _ => {
+                let session = serde_json::from_str::<LoginResponse>(body)?.session;
+                let cookie = format!("{}={}", session.name, session.value);
+                *self.cookie.lock().unwrap() = Some(session);
+                Ok(cookie)
+            }
+        }
+    }
+}
@@ -1,7 +1,10 @@
 //! Interfaces for accessing and managing transition
 
 // Ours
-use crate::{Error, Jira, Result, TransitionOption, TransitionOptions, TransitionTriggerOptions};
+use crate::{
+    field_errors, EmptyResponse, Jira, Result, TransitionOption, TransitionOptions,
+    TransitionTriggerOptions,
+};
 
 /// issue transition interface
 #[derive(Debug)]
@@ -32,17 +35,16 @@ impl Transitions {
     }
 
     /// trigger a issue transition
-    /// to transition with a resolution use TransitionTrigger::builder(id).resolution(name)
+    /// to transition with a resolution use TransitionTrigger::builder(id).resolution(name).
+    /// fields which fail validation are reported as an `Error::InvalidFields`
     pub fn trigger(&self, trans: TransitionTriggerOptions) -> Result<()> {
         self.jira
-            .post::<(), TransitionTriggerOptions>(
+            .post::<EmptyResponse, TransitionTriggerOptions>(
                 "api",
                 &format!("/issue/{}/transitions", self.key),
                 trans,
             )
-            .or_else(|e| match e {
-                Error::Serde(_) => Ok(()),
-                e => Err(e),
-            })
+            .map_err(field_errors)?;
+        Ok(())
     }
 }
@@ -0,0 +1,81 @@
+//! Pluggable http transports
+//!
+//! Every request a `Jira` client makes is handed to a `Transport`. By default
+//! this is a `ReqwestTransport`, but any implementation may be supplied with
+//! `Jira::from_transport`, ie. an in-memory fake for tests, a custom proxy or
+//! mTLS stack, or a wrapper which records traffic.
+
+// Third party
+use reqwest::blocking::Client;
+use std::fmt::Debug;
+use std::io::Read;
+
+pub use reqwest::header::HeaderMap;
+pub use reqwest::{Method, StatusCode};
+
+// Ours
+use crate::Result;
+
+/// an http request to be sent by a transport
+#[derive(Clone, Debug)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: HeaderMap,
+    pub body: Option<Vec<u8>>,
+}
+
+/// an http response received by a transport
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    /// the response body, which may be streamed
+    pub body: Box<dyn Read + Send>,
+}
+
+impl Debug for HttpResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("HttpResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish()
+    }
+}
+
+/// sends http requests on behalf of a `Jira` client
+pub trait Transport: Debug + Send + Sync {
+    /// sends a request, returning its response. responses with error statuses
+    /// should be returned as responses rather than errors
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+/// the default transport, backed by a blocking reqwest client
+#[derive(Clone, Debug, Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    /// creates a new instance of a transport using a specified reqwest client
+    pub fn new(client: Client) -> ReqwestTransport {
+        ReqwestTransport { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut builder = self
+            .client
+            .request(request.method, &request.url)
+            .headers(request.headers);
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+        let res = builder.send()?;
+        Ok(HttpResponse {
+            status: res.status(),
+            headers: res.headers().clone(),
+            body: Box::new(res),
+        })
+    }
+}
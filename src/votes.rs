@@ -0,0 +1,65 @@
+//! Interfaces for accessing and managing issue votes
+
+// Third party
+use reqwest::Method;
+
+// Ours
+use crate::{EmptyResponse, Jira, Result, User};
+
+/// issue votes interface
+#[derive(Debug)]
+pub struct Votes {
+    jira: Jira,
+    key: String,
+}
+
+/// users who voted for an issue
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct VoteResults {
+    pub votes: u64,
+    /// whether the current user has voted for the issue
+    pub has_voted: bool,
+    /// the voters, which are only listed for users with permission to view them
+    #[serde(default)]
+    pub voters: Vec<User>,
+}
+
+impl Votes {
+    pub fn new<K>(jira: &Jira, key: K) -> Votes
+    where
+        K: Into<String>,
+    {
+        Votes {
+            jira: jira.clone(),
+            key: key.into(),
+        }
+    }
+
+    /// returns the votes for the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getVotes
+    pub fn list(&self) -> Result<VoteResults> {
+        self.jira.get("api", &format!("/issue/{}/votes", self.key))
+    }
+
+    /// votes for the issue as the current user
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-addVote
+    pub fn vote(&self) -> Result<()> {
+        // jira expects no body at all, rather than a json null
+        self.jira.request::<EmptyResponse>(
+            Method::POST,
+            "api",
+            &format!("/issue/{}/votes", self.key),
+            None,
+        )?;
+        Ok(())
+    }
+
+    /// removes the current user's vote for the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-removeVote
+    pub fn unvote(&self) -> Result<()> {
+        self.jira
+            .delete::<EmptyResponse>("api", &format!("/issue/{}/votes", self.key))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+//! Interfaces for accessing and managing issue watchers
+
+// Ours
+use crate::{Account, EmptyResponse, Jira, Result, User};
+
+/// issue watchers interface
+#[derive(Debug)]
+pub struct Watchers {
+    jira: Jira,
+    key: String,
+}
+
+/// users watching an issue
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WatcherResults {
+    /// whether the current user is watching the issue
+    pub is_watching: bool,
+    pub watch_count: u64,
+    pub watchers: Vec<User>,
+}
+
+impl Watchers {
+    pub fn new<K>(jira: &Jira, key: K) -> Watchers
+    where
+        K: Into<String>,
+    {
+        Watchers {
+            jira: jira.clone(),
+            key: key.into(),
+        }
+    }
+
+    /// returns the users watching the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getIssueWatchers
+    pub fn list(&self) -> Result<WatcherResults> {
+        self.jira
+            .get("api", &format!("/issue/{}/watchers", self.key))
+    }
+
+    /// adds a user, by name or `Account`, as a watcher of the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-addWatcher
+    pub fn add<U>(&self, user: U) -> Result<()>
+    where
+        U: Into<Account>,
+    {
+        self.jira.post::<EmptyResponse, _>(
+            "api",
+            &format!("/issue/{}/watchers", self.key),
+            // jira expects the name, or account id, as a json string
+            String::from(user.into().param().1),
+        )?;
+        Ok(())
+    }
+
+    /// removes a user, by name or `Account`, as a watcher of the issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-removeWatcher
+    pub fn remove<U>(&self, user: U) -> Result<()>
+    where
+        U: Into<Account>,
+    {
+        self.jira
+            .delete::<EmptyResponse>("api", &remove_path(&self.key, &user.into()))?;
+        Ok(())
+    }
+}
+
+pub(crate) fn remove_path(key: &str, user: &Account) -> String {
+    let (param, value) = user.param();
+    format!(
+        "/issue/{}/watchers?{}",
+        key,
+        url::form_urlencoded::Serializer::new(String::new())
+            .append_pair(param, value)
+            .finish()
+    )
+}
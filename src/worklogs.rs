@@ -0,0 +1,320 @@
+//! Interfaces for accessing and managing issue worklogs
+
+// Third party
+use url::form_urlencoded;
+
+// Ours
+use crate::{EmptyResponse, Jira, Result, SearchOptions, User, Visibility};
+
+/// the most worklogs jira returns for a single `/worklog/list` request
+pub const WORKLOG_LIST_LIMIT: usize = 1000;
+
+/// worklogs interface
+#[derive(Debug)]
+pub struct Worklogs {
+    jira: Jira,
+}
+
+/// time logged against an issue
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Worklog {
+    #[serde(rename = "self")]
+    pub self_link: String,
+    pub id: String,
+    pub issue_id: Option<String>,
+    pub author: Option<User>,
+    pub update_author: Option<User>,
+    pub comment: Option<String>,
+    pub created: String,
+    pub updated: String,
+    pub started: String,
+    /// the time spent in jira's duration format, ie. "3h 20m"
+    pub time_spent: Option<String>,
+    pub time_spent_seconds: u64,
+    pub visibility: Option<Visibility>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WorklogResults {
+    pub start_at: u64,
+    pub max_results: u64,
+    pub total: u64,
+    pub worklogs: Vec<Worklog>,
+}
+
+/// the body of a new or edited worklog
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AddWorklog {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// when the work started, ie. "2019-01-01T09:00:00.000+0000"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_spent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_spent_seconds: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+}
+
+impl AddWorklog {
+    /// creates a new worklog for time spent in jira's duration format, ie. "3h 20m"
+    pub fn new<T>(time_spent: T) -> AddWorklog
+    where
+        T: Into<String>,
+    {
+        AddWorklog {
+            comment: None,
+            started: None,
+            time_spent: Some(time_spent.into()),
+            time_spent_seconds: None,
+            visibility: None,
+        }
+    }
+
+    /// creates a new worklog for a number of seconds spent
+    pub fn seconds(time_spent_seconds: u64) -> AddWorklog {
+        AddWorklog {
+            time_spent: None,
+            time_spent_seconds: Some(time_spent_seconds),
+            ..AddWorklog::new("")
+        }
+    }
+
+    pub fn comment<C>(mut self, comment: C) -> AddWorklog
+    where
+        C: Into<String>,
+    {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// when the work started, ie. "2019-01-01T09:00:00.000+0000". defaults to now
+    pub fn started<S>(mut self, started: S) -> AddWorklog
+    where
+        S: Into<String>,
+    {
+        self.started = Some(started.into());
+        self
+    }
+
+    /// restricts who may see the worklog
+    pub fn visibility(mut self, visibility: Visibility) -> AddWorklog {
+        self.visibility = Some(visibility);
+        self
+    }
+}
+
+/// how the remaining estimate of an issue changes when a worklog is
+/// added or deleted
+#[derive(Debug, Clone, PartialEq)]
+pub enum AdjustEstimate {
+    /// reduces the estimate by time spent when adding, and restores it when deleting
+    Auto,
+    /// leaves the estimate unchanged
+    Leave,
+    /// sets the estimate to a new duration, ie. "2d"
+    New(String),
+    /// reduces the estimate by a duration when adding, or increases it when deleting
+    Manual(String),
+}
+
+/// how the remaining estimate of an issue changes when a worklog is updated.
+/// jira doesn't support manual adjustments of updated worklogs
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpdateEstimate {
+    /// adjusts the estimate by the change in time spent
+    Auto,
+    /// leaves the estimate unchanged
+    Leave,
+    /// sets the estimate to a new duration, ie. "2d"
+    New(String),
+}
+
+impl From<UpdateEstimate> for AdjustEstimate {
+    fn from(adjust: UpdateEstimate) -> AdjustEstimate {
+        match adjust {
+            UpdateEstimate::Auto => AdjustEstimate::Auto,
+            UpdateEstimate::Leave => AdjustEstimate::Leave,
+            UpdateEstimate::New(estimate) => AdjustEstimate::New(estimate),
+        }
+    }
+}
+
+impl AdjustEstimate {
+    /// query parameters for a request. `manual` names the parameter carrying
+    /// a manual adjustment, which differs between adding and deleting
+    fn query(&self, manual: &str) -> String {
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        match *self {
+            AdjustEstimate::Auto => query.append_pair("adjustEstimate", "auto"),
+            AdjustEstimate::Leave => query.append_pair("adjustEstimate", "leave"),
+            AdjustEstimate::New(ref estimate) => query
+                .append_pair("adjustEstimate", "new")
+                .append_pair("newEstimate", estimate),
+            AdjustEstimate::Manual(ref adjustment) => query
+                .append_pair("adjustEstimate", "manual")
+                .append_pair(manual, adjustment),
+        };
+        query.finish()
+    }
+}
+
+/// a change to a worklog, as listed by `/worklog/updated`
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WorklogChange {
+    pub worklog_id: u64,
+    /// milliseconds since the epoch
+    pub updated_time: u64,
+}
+
+/// a page of worklog changes
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WorklogChanges {
+    pub values: Vec<WorklogChange>,
+    /// milliseconds since the epoch
+    pub since: u64,
+    /// milliseconds since the epoch. the `since` of the next page
+    pub until: u64,
+    pub last_page: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub(crate) struct WorklogIds<'a> {
+    pub ids: &'a [u64],
+}
+
+pub(crate) fn worklogs_path(key: &str, options: &SearchOptions) -> String {
+    let query = options.serialize().unwrap_or_default();
+    if query.is_empty() {
+        format!("/issue/{}/worklog", key)
+    } else {
+        format!("/issue/{}/worklog?{}", key, query)
+    }
+}
+
+pub(crate) fn add_path(key: &str, adjust: &AdjustEstimate) -> String {
+    format!("/issue/{}/worklog?{}", key, adjust.query("reduceBy"))
+}
+
+pub(crate) fn worklog_path(key: &str, id: &str, adjust: &AdjustEstimate) -> String {
+    format!(
+        "/issue/{}/worklog/{}?{}",
+        key,
+        id,
+        adjust.query("increaseBy")
+    )
+}
+
+impl Worklogs {
+    pub fn new(jira: &Jira) -> Worklogs {
+        Worklogs { jira: jira.clone() }
+    }
+
+    /// returns a single page of the worklogs of an issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-getIssueWorklog
+    pub fn list<K>(&self, key: K, options: &SearchOptions) -> Result<WorklogResults>
+    where
+        K: Into<String>,
+    {
+        self.jira.get("api", &worklogs_path(&key.into(), options))
+    }
+
+    /// returns a single worklog of an issue
+    pub fn get<K, I>(&self, key: K, id: I) -> Result<Worklog>
+    where
+        K: Into<String>,
+        I: Into<String>,
+    {
+        self.jira.get(
+            "api",
+            &format!("/issue/{}/worklog/{}", key.into(), id.into()),
+        )
+    }
+
+    /// logs time against an issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-addWorklog
+    pub fn add<K>(&self, key: K, worklog: AddWorklog, adjust: AdjustEstimate) -> Result<Worklog>
+    where
+        K: Into<String>,
+    {
+        self.jira
+            .post("api", &add_path(&key.into(), &adjust), worklog)
+    }
+
+    /// replaces a worklog of an issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-updateWorklog
+    pub fn update<K, I>(
+        &self,
+        key: K,
+        id: I,
+        worklog: AddWorklog,
+        adjust: UpdateEstimate,
+    ) -> Result<Worklog>
+    where
+        K: Into<String>,
+        I: Into<String>,
+    {
+        self.jira.put(
+            "api",
+            &worklog_path(&key.into(), &id.into(), &adjust.into()),
+            worklog,
+        )
+    }
+
+    /// deletes a worklog of an issue
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/issue-deleteWorklog
+    pub fn delete<K, I>(&self, key: K, id: I, adjust: AdjustEstimate) -> Result<()>
+    where
+        K: Into<String>,
+        I: Into<String>,
+    {
+        self.jira
+            .delete::<EmptyResponse>("api", &worklog_path(&key.into(), &id.into(), &adjust))?;
+        Ok(())
+    }
+
+    /// returns a single page of the ids of worklogs updated since a time,
+    /// in milliseconds since the epoch
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/worklog-getIdsOfWorklogsModifiedSince
+    pub fn updated(&self, since: u64) -> Result<WorklogChanges> {
+        self.jira
+            .get("api", &format!("/worklog/updated?since={}", since))
+    }
+
+    /// returns worklogs by id, across issues
+    /// https://docs.atlassian.com/software/jira/docs/api/REST/latest/#api/2/worklog-getWorklogsForIds
+    pub fn list_by_ids(&self, ids: &[u64]) -> Result<Vec<Worklog>> {
+        let mut worklogs = vec![];
+        for chunk in ids.chunks(WORKLOG_LIST_LIMIT) {
+            worklogs.extend(self.jira.post::<Vec<Worklog>, _>(
+                "api",
+                "/worklog/list",
+                WorklogIds { ids: chunk },
+            )?);
+        }
+        Ok(worklogs)
+    }
+
+    /// returns all worklogs updated since a time, in milliseconds since the epoch,
+    /// following every page of `updated` changes
+    pub fn updated_since(&self, since: u64) -> Result<Vec<Worklog>> {
+        let mut ids = vec![];
+        let mut since = since;
+        loop {
+            let changes = self.updated(since)?;
+            ids.extend(changes.values.iter().map(|change| change.worklog_id));
+            if changes.last_page || changes.until <= since {
+                break;
+            }
+            since = changes.until;
+        }
+        self.list_by_ids(&ids)
+    }
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "async")]
+
+extern crate futures;
+extern crate goji;
+extern crate serde_json;
+extern crate tokio;
+
+mod common;
+
+use common::{response, stub, stub_many};
+use futures::stream::TryStreamExt;
+use goji::asynchronous::Jira;
+use goji::*;
+
+fn jira(host: String) -> Jira {
+    Jira::new(host, Credentials::Basic("user".into(), "pwd".into())).unwrap()
+}
+
+#[tokio::test]
+async fn get_issue() {
+    let (host, rx) = stub(&response(
+        "200 OK",
+        r#"{"self": "http://jira.com/rest/api/latest/issue/1", "id": "1", "key": "TEST-1", "fields": {}}"#,
+    ));
+    let issue = jira(host).issues().get("TEST-1").await.unwrap();
+
+    assert_eq!(issue.key, "TEST-1");
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("GET /rest/api/latest/issue/TEST-1 HTTP/1.1"));
+}
+
+#[tokio::test]
+async fn search_streams_consecutive_pages() {
+    let issue = |key: &str| {
+        format!(
+            r#"{{"self": "http://jira.com/rest/api/latest/issue/{0}", "id": "{0}", "key": "{0}", "fields": {{}}}}"#,
+            key
+        )
+    };
+    let (host, rx) = stub_many(vec![
+        response(
+            "200 OK",
+            &format!(
+                r#"{{"total": 3, "maxResults": 2, "startAt": 0, "issues": [{}, {}]}}"#,
+                issue("TEST-1"),
+                issue("TEST-2")
+            ),
+        ),
+        response(
+            "200 OK",
+            &format!(
+                r#"{{"total": 3, "maxResults": 2, "startAt": 2, "issues": [{}]}}"#,
+                issue("TEST-3")
+            ),
+        ),
+    ]);
+    let issues = jira(host)
+        .search()
+        .stream("project = TEST", &Default::default())
+        .try_collect::<Vec<Issue>>()
+        .await
+        .unwrap();
+    let requests = rx.iter().take(2).collect::<Vec<_>>();
+
+    assert_eq!(
+        issues.iter().map(|i| i.key.as_str()).collect::<Vec<_>>(),
+        vec!["TEST-1", "TEST-2", "TEST-3"]
+    );
+    assert!(requests[1].contains("startAt=2"));
+}
+
+#[tokio::test]
+async fn stream_surfaces_errors() {
+    let (host, _rx) = stub(&response("401 Unauthorized", ""));
+    let result = jira(host)
+        .boards()
+        .stream(&Default::default())
+        .try_collect::<Vec<_>>()
+        .await;
+
+    match result {
+        Err(Error::Unauthorized) => (),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn upload_attachment_reads_async_content() {
+    let (host, rx) = stub(&response("200 OK", "[]"));
+    let uploaded = jira(host)
+        .attachments()
+        .upload("TEST-1", "notes.txt", &b"some notes"[..])
+        .await
+        .unwrap();
+
+    assert!(uploaded.is_empty());
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with("POST /rest/api/latest/issue/TEST-1/attachments HTTP/1.1"));
+    assert!(request.contains("\r\n\r\nsome notes\r\n"));
+}
+
+#[tokio::test]
+async fn download_attachment_writes_async_content() {
+    let (host, rx) = stub(&response("200 OK", "some notes"));
+    let attachment: Attachment = serde_json::from_str(&format!(
+        r#"{{"id":"10000","self":"{0}/rest/api/2/attachment/10000","filename":"notes.txt","author":{{"active":true,"displayName":"User","self":"{0}/rest/api/2/user?username=user"}},"created":"2019-01-01T00:00:00.000+0000","size":10,"mimeType":"text/plain","content":"{0}/secure/attachment/10000/notes.txt"}}"#,
+        host
+    ))
+    .unwrap();
+    let mut downloaded = Vec::new();
+    let written = jira(host)
+        .attachments()
+        .download(&attachment, &mut downloaded)
+        .await
+        .unwrap();
+
+    assert_eq!(written, 10);
+    assert_eq!(downloaded, b"some notes");
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("GET /secure/attachment/10000/notes.txt HTTP/1.1"));
+}
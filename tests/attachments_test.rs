@@ -0,0 +1,63 @@
+extern crate goji;
+
+mod common;
+
+use common::{response, stub, stub_many};
+use goji::*;
+
+fn jira(host: String) -> Jira {
+    Jira::new(host, Credentials::Basic("user".into(), "pwd".into())).unwrap()
+}
+
+#[test]
+fn upload_attachment_sends_multipart_without_xsrf_check() {
+    let (host, rx) = stub(&response("200 OK", "[]"));
+    let uploaded = jira(host)
+        .attachments()
+        .upload("TEST-1", "notes.txt", &b"some notes"[..])
+        .unwrap();
+
+    assert!(uploaded.is_empty());
+    let request = rx.recv().unwrap();
+    let lowercase = request.to_lowercase();
+    assert!(request.starts_with("POST /rest/api/latest/issue/TEST-1/attachments HTTP/1.1"));
+    assert!(lowercase.contains("x-atlassian-token: no-check"));
+    assert!(lowercase.contains("content-type: multipart/form-data; boundary="));
+    assert!(request.contains(
+        "Content-Disposition: form-data; name=\"file\"; filename=\"notes.txt\"\r\nContent-Type: application/octet-stream\r\n\r\nsome notes\r\n"
+    ));
+}
+
+#[test]
+fn attachments_are_fetched_downloaded_and_deleted() {
+    let (content_host, content_rx) = stub(&response("200 OK", "some notes"));
+    let attachment = format!(
+        r#"{{"id":"10000","self":"http://jira/rest/api/2/attachment/10000","filename":"notes.txt","author":{{"active":true,"avatarUrls":{{}},"displayName":"User","emailAddress":"user@example.com","name":"user","self":"http://jira/rest/api/2/user?username=user"}},"created":"2019-01-01T00:00:00.000+0000","size":10,"mimeType":"text/plain","content":"{}/secure/attachment/10000/notes.txt"}}"#,
+        content_host
+    );
+    let (host, rx) = stub_many(vec![
+        response("200 OK", &attachment),
+        response("204 No Content", ""),
+    ]);
+    let attachments = jira(host).attachments();
+
+    let attachment = attachments.get("10000").unwrap();
+    let mut downloaded = Vec::new();
+    let written = attachments.download(&attachment, &mut downloaded).unwrap();
+    assert_eq!(written, 10);
+    assert_eq!(downloaded, b"some notes");
+    attachments.delete("10000").unwrap();
+
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("GET /rest/api/latest/attachment/10000 HTTP/1.1"));
+    assert!(content_rx
+        .recv()
+        .unwrap()
+        .starts_with("GET /secure/attachment/10000/notes.txt HTTP/1.1"));
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("DELETE /rest/api/latest/attachment/10000 HTTP/1.1"));
+}
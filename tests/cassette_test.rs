@@ -0,0 +1,132 @@
+extern crate goji;
+extern crate serde_json;
+
+mod common;
+
+use common::{response, stub, stub_many};
+use goji::cassette::{Cassette, Recorder, Replayer};
+use goji::*;
+use std::env;
+use std::fs;
+
+const ISSUE: &str = r#"{"self": "http://jira.com/rest/api/latest/issue/1", "id": "1", "key": "TEST-1", "fields": {"summary": "recorded"}}"#;
+
+#[test]
+fn records_and_replays_interactions() {
+    let path = env::temp_dir().join(format!("goji-cassette-{}.json", std::process::id()));
+    let (host, _rx) = stub(&response("200 OK", ISSUE));
+
+    let recorder = Recorder::new(ReqwestTransport::default(), &path).scrub("s3cret");
+    let jira = Jira::from_transport(
+        host.clone(),
+        Credentials::Basic("user".into(), "s3cret".into()),
+        recorder,
+    )
+    .unwrap();
+    assert_eq!(
+        jira.issues()
+            .get("TEST-1")
+            .unwrap()
+            .summary()
+            .transpose()
+            .unwrap(),
+        Some("recorded".to_owned())
+    );
+
+    let recorded = fs::read_to_string(&path).unwrap();
+    assert!(!recorded.to_lowercase().contains("authorization"));
+    let cassette = Cassette::load(&path).unwrap();
+    assert_eq!(cassette.interactions.len(), 1);
+    assert_eq!(cassette.interactions[0].request.method, "GET");
+    assert_eq!(cassette.interactions[0].response.status, 200);
+
+    // the stub only answers once, so this must be served from the cassette
+    let jira = Jira::from_transport(
+        host,
+        Credentials::Basic("user".into(), "s3cret".into()),
+        Replayer::from_file(&path).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        jira.issues()
+            .get("TEST-1")
+            .unwrap()
+            .summary()
+            .transpose()
+            .unwrap(),
+        Some("recorded".to_owned())
+    );
+    match jira.issues().get("TEST-1") {
+        Err(Error::IO(_)) => (),
+        other => panic!("unexpected {:?}", other),
+    }
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn scrubs_secrets_from_bodies() {
+    let path = env::temp_dir().join(format!("goji-scrub-{}.json", std::process::id()));
+    let (host, _rx) = stub(&response(
+        "200 OK",
+        r#"{"session":{"name":"JSESSIONID","value":"abc"}}"#,
+    ));
+
+    let recorder = Recorder::new(ReqwestTransport::default(), &path).scrub("s3cret");
+    let _ = recorder.send(HttpRequest {
+        method: transport::Method::POST,
+        url: format!("{}/rest/auth/1/session", host),
+        headers: Default::default(),
+        body: Some(br#"{"username":"user","password":"s3cret"}"#.to_vec()),
+    });
+
+    let cassette = Cassette::load(&path).unwrap();
+    assert_eq!(
+        cassette.interactions[0].request.body,
+        Some(r#"{"password":"[scrubbed]","username":"user"}"#.to_owned())
+    );
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn redacts_session_logins() {
+    let path = env::temp_dir().join(format!("goji-login-{}.json", std::process::id()));
+    let (host, _rx) = stub_many(vec![
+        response(
+            "200 OK",
+            r#"{"session":{"name":"JSESSIONID","value":"t0ken"}}"#,
+        ),
+        response("200 OK", ISSUE),
+    ]);
+
+    let recorder = Recorder::new(ReqwestTransport::default(), &path);
+    let jira = Jira::from_transport(
+        host.clone(),
+        Credentials::Session("user".into(), "s3cret".into()),
+        recorder,
+    )
+    .unwrap();
+    jira.issues().get("TEST-1").unwrap();
+
+    let recorded = fs::read_to_string(&path).unwrap();
+    assert!(!recorded.contains("t0ken"));
+    assert!(!recorded.contains("s3cret"));
+    let cassette = Cassette::load(&path).unwrap();
+    assert_eq!(cassette.interactions.len(), 2);
+    assert!(cassette.interactions[0]
+        .request
+        .url
+        .ends_with("/rest/auth/1/session"));
+
+    // logins are still replayed, with a placeholder session
+    let jira = Jira::from_transport(
+        host,
+        Credentials::Session("user".into(), "s3cret".into()),
+        Replayer::from_file(&path).unwrap(),
+    )
+    .unwrap();
+    assert!(jira.issues().get("TEST-1").is_ok());
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,502 @@
+extern crate goji;
+extern crate serde_json;
+
+mod common;
+
+use common::{response, stub, stub_many};
+use goji::*;
+use std::time::Duration;
+
+fn jira(host: String) -> Jira {
+    Jira::new(host, Credentials::Basic("user".into(), "pwd".into())).unwrap()
+}
+
+#[test]
+fn delete_handles_no_content() {
+    let (host, rx) = stub(&response("204 No Content", ""));
+    let result: Result<EmptyResponse> = jira(host).delete("api", "/issue/TEST-1");
+
+    assert!(result.is_ok());
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("DELETE /rest/api/latest/issue/TEST-1 HTTP/1.1"));
+}
+
+#[test]
+fn put_sends_json_body() {
+    let (host, rx) = stub(&response("204 No Content", ""));
+    let result: Result<()> = jira(host).put(
+        "api",
+        "/issue/TEST-1",
+        serde_json::json!({ "fields": { "summary": "updated" } }),
+    );
+    let request = rx.recv().unwrap();
+
+    assert!(result.is_ok());
+    assert!(request.starts_with("PUT /rest/api/latest/issue/TEST-1 HTTP/1.1"));
+    assert!(request.ends_with(r#"{"fields":{"summary":"updated"}}"#));
+}
+
+#[test]
+fn patch_deserializes_response() {
+    let (host, rx) = stub(&response("200 OK", r#"{"id":"1000"}"#));
+    let result: serde_json::Value = jira(host)
+        .patch("agile", "/sprint/1", serde_json::json!({ "name": "s1" }))
+        .unwrap();
+
+    assert_eq!(result["id"], "1000");
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("PATCH /rest/agile/latest/sprint/1 HTTP/1.1"));
+}
+
+#[test]
+fn bearer_credentials_send_token() {
+    let (host, rx) = stub(&response("204 No Content", ""));
+    let jira = Jira::new(host, Credentials::Bearer("my-pat".into())).unwrap();
+    let _: EmptyResponse = jira.get("api", "/myself").unwrap();
+
+    assert!(rx
+        .recv()
+        .unwrap()
+        .to_lowercase()
+        .contains("authorization: bearer my-pat\r\n"));
+}
+
+#[test]
+fn api_token_credentials_send_basic_auth() {
+    let (host, rx) = stub(&response("204 No Content", ""));
+    let jira = Jira::new(
+        host,
+        Credentials::ApiToken("me@example.com".into(), "token".into()),
+    )
+    .unwrap();
+    let _: EmptyResponse = jira.get("api", "/myself").unwrap();
+
+    // base64("me@example.com:token")
+    assert!(rx.recv().unwrap().contains("bWVAZXhhbXBsZS5jb206dG9rZW4="));
+}
+
+#[test]
+fn session_credentials_login_once_and_renew() {
+    let (host, rx) = stub_many(vec![
+        response(
+            "200 OK",
+            r#"{"session":{"name":"JSESSIONID","value":"first"}}"#,
+        ),
+        response("204 No Content", ""),
+        response("204 No Content", ""),
+        response("401 Unauthorized", ""),
+        response(
+            "200 OK",
+            r#"{"session":{"name":"JSESSIONID","value":"second"}}"#,
+        ),
+        response("204 No Content", ""),
+    ]);
+    let jira = Jira::new(host, Credentials::Session("user".into(), "pwd".into())).unwrap();
+    for _ in 0..3 {
+        let _: EmptyResponse = jira.get("api", "/myself").unwrap();
+    }
+    let requests = rx.iter().take(6).collect::<Vec<_>>();
+
+    assert!(requests[0].starts_with("POST /rest/auth/1/session HTTP/1.1"));
+    assert!(requests[0].ends_with(r#"{"username":"user","password":"pwd"}"#));
+    assert!(requests[1].contains("JSESSIONID=first"));
+    assert!(requests[2].contains("JSESSIONID=first"));
+    assert!(requests[3].contains("JSESSIONID=first"));
+    assert!(requests[4].starts_with("POST /rest/auth/1/session HTTP/1.1"));
+    assert!(requests[5].contains("JSESSIONID=second"));
+    assert!(!requests[5].to_lowercase().contains("authorization:"));
+}
+
+#[test]
+fn session_login_reports_server_errors() {
+    let (host, _rx) = stub(
+        "HTTP/1.1 503 Service Unavailable\r\nContent-Type: text/html\r\nRetry-After: 120\r\nContent-Length: 17\r\n\r\n<html>down</html>",
+    );
+    let jira = Jira::new(host, Credentials::Session("user".into(), "pwd".into())).unwrap();
+
+    match jira.get::<EmptyResponse>("api", "/myself") {
+        Err(Error::Server {
+            code,
+            headers,
+            body,
+        }) => {
+            assert_eq!(code.as_u16(), 503);
+            assert_eq!(headers["retry-after"], "120");
+            assert_eq!(body, "<html>down</html>");
+        }
+        other => panic!("expected server error, got {:?}", other),
+    }
+}
+
+#[test]
+fn session_login_reports_denial_reasons() {
+    let (host, _rx) = stub(
+        "HTTP/1.1 403 Forbidden\r\nX-Authentication-Denied-Reason: CAPTCHA_CHALLENGE; login-url=http://jira/login.jsp\r\nContent-Length: 0\r\n\r\n",
+    );
+    let jira = Jira::new(host, Credentials::Session("user".into(), "pwd".into())).unwrap();
+
+    match jira.get::<EmptyResponse>("api", "/myself") {
+        Err(Error::AuthenticationDenied { reason, .. }) => assert_eq!(
+            reason.as_deref(),
+            Some("CAPTCHA_CHALLENGE; login-url=http://jira/login.jsp")
+        ),
+        other => panic!("expected denied authentication, got {:?}", other),
+    }
+}
+
+fn retrying(host: String) -> Jira {
+    jira(host).with_retry_policy(
+        RetryPolicy::builder()
+            .initial_backoff(Duration::from_millis(1))
+            .build(),
+    )
+}
+
+#[test]
+fn retries_unavailable_and_rate_limited_responses() {
+    let (host, rx) = stub_many(vec![
+        response("503 Service Unavailable", "<html>down</html>"),
+        "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\n\r\n".to_owned(),
+        response("200 OK", r#"{"id":"1000"}"#),
+    ]);
+    let result: serde_json::Value = retrying(host).get("api", "/issue/TEST-1").unwrap();
+
+    assert_eq!(result["id"], "1000");
+    assert_eq!(rx.iter().take(3).count(), 3);
+}
+
+#[test]
+fn does_not_retry_post_by_default() {
+    let (host, rx) = stub_many(vec![
+        response("503 Service Unavailable", ""),
+        response("201 Created", r#"{"id":"1000"}"#),
+    ]);
+    let result: Result<CreateResponse> =
+        retrying(host).post("api", "/issue", serde_json::json!({}));
+
+    assert!(result.is_err());
+    assert!(rx.recv().is_ok());
+    assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+}
+
+#[test]
+fn gives_up_when_retry_after_exceeds_max_backoff() {
+    let (host, rx) = stub_many(vec![
+        "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 120\r\nContent-Length: 0\r\n\r\n"
+            .to_owned(),
+        response("200 OK", r#"{"id":"1000"}"#),
+    ]);
+    let result: Result<serde_json::Value> = retrying(host).get("api", "/issue/TEST-1");
+
+    match result {
+        Err(Error::Fault { code, .. }) => assert_eq!(code.as_u16(), 429),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(rx.recv().is_ok());
+    assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+}
+
+#[test]
+fn retries_session_logins() {
+    let (host, rx) = stub_many(vec![
+        response("503 Service Unavailable", "<html>down</html>"),
+        response(
+            "200 OK",
+            r#"{"session":{"name":"JSESSIONID","value":"first"}}"#,
+        ),
+        response("401 Unauthorized", ""),
+        response("503 Service Unavailable", "<html>down</html>"),
+        response(
+            "200 OK",
+            r#"{"session":{"name":"JSESSIONID","value":"second"}}"#,
+        ),
+        response("204 No Content", ""),
+    ]);
+    let jira = Jira::new(host, Credentials::Session("user".into(), "pwd".into()))
+        .unwrap()
+        .with_retry_policy(
+            RetryPolicy::builder()
+                .initial_backoff(Duration::from_millis(1))
+                .build(),
+        );
+    let _: EmptyResponse = jira.get("api", "/myself").unwrap();
+    let requests = rx.iter().take(6).collect::<Vec<_>>();
+
+    for &n in &[0, 1, 3, 4] {
+        assert!(requests[n].starts_with("POST /rest/auth/1/session HTTP/1.1"));
+    }
+    assert!(requests[2].contains("JSESSIONID=first"));
+    assert!(requests[5].contains("JSESSIONID=second"));
+}
+
+#[test]
+fn server_errors_keep_raw_response() {
+    let (host, _rx) = stub(&response("502 Bad Gateway", "<html>bad gateway</html>"));
+    let result: Result<serde_json::Value> = jira(host).get("api", "/issue/TEST-1");
+
+    match result {
+        Err(Error::Server {
+            code,
+            headers,
+            body,
+        }) => {
+            assert_eq!(code, reqwest::StatusCode::BAD_GATEWAY);
+            assert_eq!(headers["content-type"], "application/json");
+            assert_eq!(body, "<html>bad gateway</html>");
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn faults_tolerate_non_json_bodies() {
+    let (host, _rx) = stub(&response("400 Bad Request", "<html>rejected</html>"));
+    let result: Result<serde_json::Value> = jira(host).get("api", "/issue/TEST-1");
+
+    match result {
+        Err(Error::Fault { code, errors }) => {
+            assert_eq!(code, reqwest::StatusCode::BAD_REQUEST);
+            assert_eq!(errors.error_messages, vec!["<html>rejected</html>"]);
+            assert!(errors.errors.is_empty());
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn faults_parse_partial_error_bodies() {
+    let (host, _rx) = stub(&response(
+        "400 Bad Request",
+        r#"{"errors":{"summary":"required"}}"#,
+    ));
+    let result: Result<serde_json::Value> = jira(host).get("api", "/issue/TEST-1");
+
+    match result {
+        Err(Error::Fault { errors, .. }) => {
+            assert!(errors.error_messages.is_empty());
+            assert_eq!(errors.errors["summary"], "required");
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn update_issue_reports_invalid_fields() {
+    let (host, _rx) = stub(&response(
+        "400 Bad Request",
+        r#"{"errorMessages":[],"errors":{"summary":"You must specify a summary of the issue."}}"#,
+    ));
+    let result = jira(host)
+        .issues()
+        .update("TEST-1", EditIssue::builder().summary("").build());
+
+    match result {
+        Err(Error::InvalidFields(errors)) => assert_eq!(
+            errors.errors["summary"],
+            "You must specify a summary of the issue."
+        ),
+        other => panic!("expected invalid fields, got {:?}", other),
+    }
+}
+
+#[test]
+fn trigger_transition_reports_invalid_fields() {
+    let (host, _rx) = stub(&response(
+        "400 Bad Request",
+        r#"{"errorMessages":[],"errors":{"resolution":"Resolution is required."}}"#,
+    ));
+    let result = jira(host)
+        .transitions("TEST-1")
+        .trigger(TransitionTriggerOptions::new("31"));
+
+    match result {
+        Err(Error::InvalidFields(errors)) => {
+            assert_eq!(errors.errors["resolution"], "Resolution is required.")
+        }
+        other => panic!("expected invalid fields, got {:?}", other),
+    }
+}
+
+#[test]
+fn trigger_transition_passes_other_faults_through() {
+    let (host, _rx) = stub(&response(
+        "400 Bad Request",
+        r#"{"errorMessages":["It seems that you have tried to perform a workflow operation that is not valid"],"errors":{}}"#,
+    ));
+    let result = jira(host)
+        .transitions("TEST-1")
+        .trigger(TransitionTriggerOptions::new("31"));
+
+    match result {
+        Err(Error::Fault { code, .. }) => assert_eq!(code.as_u16(), 400),
+        other => panic!("expected fault, got {:?}", other),
+    }
+}
+
+#[test]
+fn update_issue_controls_notifications() {
+    let (host, rx) = stub(&response("204 No Content", ""));
+    let result = jira(host).issues().update(
+        "TEST-1",
+        EditIssue::builder()
+            .summary("new summary")
+            .notify_users(false)
+            .build(),
+    );
+
+    assert!(result.is_ok());
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with("PUT /rest/api/latest/issue/TEST-1?notifyUsers=false HTTP/1.1"));
+    assert!(request.ends_with(r#"{"fields":{"summary":"new summary"}}"#));
+}
+
+#[test]
+fn delete_issue_sends_subtasks_option() {
+    let (host, rx) = stub(&response("204 No Content", ""));
+    let result = jira(host).issues().delete("TEST-1", true);
+
+    assert!(result.is_ok());
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("DELETE /rest/api/latest/issue/TEST-1?deleteSubtasks=true HTTP/1.1"));
+}
+
+#[test]
+fn create_issue_sends_optional_and_custom_fields() {
+    let (host, rx) = stub(&response(
+        "201 Created",
+        r#"{"id":"10000","key":"TEST-1","self":"http://jira/rest/api/2/issue/10000"}"#,
+    ));
+    let created = jira(host)
+        .issues()
+        .create(
+            CreateIssue::builder("TEST", "10002", "it broke")
+                .label("regression")
+                .field("customfield_10010", serde_json::json!({ "value": "High" }))
+                .build(),
+        )
+        .unwrap();
+
+    assert_eq!(created.key, "TEST-1");
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with("POST /rest/api/latest/issue HTTP/1.1"));
+    assert!(request.ends_with(
+        r#"{"fields":{"issuetype":{"id":"10002"},"labels":["regression"],"project":{"key":"TEST"},"summary":"it broke","customfield_10010":{"value":"High"}}}"#
+    ));
+}
+
+#[test]
+fn create_bulk_reports_element_errors() {
+    let (host, rx) = stub(&response(
+        "201 Created",
+        r#"{"issues":[{"id":"10000","key":"TEST-1","self":"http://jira/rest/api/2/issue/10000"}],"errors":[{"status":400,"elementErrors":{"errorMessages":[],"errors":{"summary":"You must specify a summary of the issue."}},"failedElementNumber":1}]}"#,
+    ));
+    let results = jira(host)
+        .issues()
+        .create_bulk(vec![
+            CreateIssue::builder("TEST", "10001", "one").build(),
+            CreateIssue::builder("TEST", "10001", "").build(),
+        ])
+        .unwrap();
+
+    assert_eq!(results.issues[0].key, "TEST-1");
+    assert_eq!(results.errors[0].failed_element_number, 1);
+    assert!(results.errors[0]
+        .element_errors
+        .errors
+        .contains_key("summary"));
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with("POST /rest/api/latest/issue/bulk HTTP/1.1"));
+    assert!(request.contains(r#"{"issueUpdates":[{"fields":"#));
+}
+
+#[test]
+fn create_bulk_reports_total_failures() {
+    let (host, _rx) = stub(&response(
+        "400 Bad Request",
+        r#"{"issues":[],"errors":[{"status":400,"elementErrors":{"errorMessages":[],"errors":{"project":"project is required"}},"failedElementNumber":0},{"status":400,"elementErrors":{"errorMessages":[],"errors":{"project":"project is required"}},"failedElementNumber":1}]}"#,
+    ));
+    let results = jira(host)
+        .issues()
+        .create_bulk(vec![
+            CreateIssue::builder("OTHER", "10001", "one").build(),
+            CreateIssue::builder("OTHER", "10001", "two").build(),
+        ])
+        .unwrap();
+
+    assert!(results.issues.is_empty());
+    assert_eq!(results.errors.len(), 2);
+    assert_eq!(results.errors[1].status, 400);
+}
+
+#[test]
+fn create_bulk_keeps_created_issues_when_a_later_chunk_fails() {
+    let created = (1..=BULK_CREATE_LIMIT)
+        .map(|n| {
+            format!(
+                r#"{{"id":"{}","key":"TEST-{}","self":"http://jira/rest/api/2/issue/{}"}}"#,
+                n, n, n
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let (host, rx) = stub_many(vec![
+        response(
+            "201 Created",
+            &format!(r#"{{"issues":[{}],"errors":[]}}"#, created),
+        ),
+        response("500 Internal Server Error", "<html>oops</html>"),
+    ]);
+    let issues = (0..BULK_CREATE_LIMIT + 10)
+        .map(|n| CreateIssue::builder("TEST", "10001", format!("issue {}", n)).build())
+        .collect::<Vec<_>>();
+
+    let results = jira(host).issues().create_bulk(issues).unwrap();
+
+    assert_eq!(results.issues.len(), BULK_CREATE_LIMIT);
+    assert_eq!(results.errors.len(), 10);
+    assert_eq!(results.errors[0].failed_element_number, BULK_CREATE_LIMIT);
+    assert_eq!(results.errors[0].status, 500);
+    assert!(rx.recv().is_ok() && rx.recv().is_ok());
+}
+
+#[test]
+fn delete_reports_issues_with_subtasks() {
+    let (host, _rx) = stub(&response(
+        "400 Bad Request",
+        r#"{"errorMessages":["The issue has subtasks"],"errors":{}}"#,
+    ));
+
+    match jira(host).issues().delete("TEST-1", false) {
+        Err(Error::IssueHasSubtasks(errors)) => {
+            assert_eq!(errors.error_messages, vec!["The issue has subtasks"])
+        }
+        other => panic!("expected subtasks error, got {:?}", other),
+    }
+}
+
+#[test]
+fn delete_passes_other_bad_requests_through() {
+    for &(body, delete_subtasks) in &[
+        (
+            r#"{"errorMessages":["Issue is locked"],"errors":{}}"#,
+            false,
+        ),
+        (
+            r#"{"errorMessages":["The issue has subtasks"],"errors":{}}"#,
+            true,
+        ),
+    ] {
+        let (host, _rx) = stub(&response("400 Bad Request", body));
+
+        match jira(host).issues().delete("TEST-1", delete_subtasks) {
+            Err(Error::Fault { code, .. }) => assert_eq!(code.as_u16(), 400),
+            other => panic!("expected fault, got {:?}", other),
+        }
+    }
+}
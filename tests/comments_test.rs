@@ -0,0 +1,76 @@
+extern crate goji;
+
+mod common;
+
+use common::{response, stub, stub_many};
+use goji::*;
+
+fn jira(host: String) -> Jira {
+    Jira::new(host, Credentials::Basic("user".into(), "pwd".into())).unwrap()
+}
+
+#[test]
+fn add_comment_sends_visibility() {
+    let (host, rx) = stub(&response(
+        "201 Created",
+        r#"{"self":"http://jira/rest/api/2/issue/10000/comment/1","id":"1","body":"hi","created":"2019-01-01T00:00:00.000+0000","updated":"2019-01-01T00:00:00.000+0000"}"#,
+    ));
+    let comment = jira(host)
+        .comments("TEST-1")
+        .add(comments::AddComment::new("hi").visibility(Visibility::group("jira-users")))
+        .unwrap();
+
+    assert_eq!(comment.id, Some("1".to_owned()));
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with("POST /rest/api/latest/issue/TEST-1/comment HTTP/1.1"));
+    assert!(
+        request.ends_with(r#"{"body":"hi","visibility":{"type":"group","value":"jira-users"}}"#)
+    );
+}
+
+#[test]
+fn comments_are_listed_updated_and_deleted() {
+    let comment = r#"{"self":"http://jira/rest/api/2/issue/10000/comment/1","id":"1","body":"edited","renderedBody":"<p>edited</p>","created":"2019-01-01T00:00:00.000+0000","updated":"2019-01-01T00:00:00.000+0000"}"#;
+    let (host, rx) = stub_many(vec![
+        response(
+            "200 OK",
+            &format!(
+                r#"{{"startAt":0,"maxResults":50,"total":1,"comments":[{}]}}"#,
+                comment
+            ),
+        ),
+        response("200 OK", comment),
+        response("204 No Content", ""),
+    ]);
+    let comments = jira(host).comments("TEST-1");
+
+    let results = comments
+        .list(
+            &SearchOptions::builder()
+                .order_by("-created")
+                .expand(vec!["renderedBody"])
+                .build(),
+        )
+        .unwrap();
+    assert_eq!(
+        results.comments[0].rendered_body,
+        Some("<p>edited</p>".to_owned())
+    );
+    comments
+        .update("1", comments::AddComment::new("edited"))
+        .unwrap();
+    comments.delete("1").unwrap();
+
+    let request = rx.recv().unwrap();
+    let request_line = request.lines().next().unwrap();
+    assert!(request_line.starts_with("GET /rest/api/latest/issue/TEST-1/comment?"));
+    assert!(request_line.contains("orderBy=-created"));
+    assert!(request_line.contains("expand=renderedBody"));
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with("PUT /rest/api/latest/issue/TEST-1/comment/1 HTTP/1.1"));
+    assert!(request.ends_with(r#"{"body":"edited"}"#));
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("DELETE /rest/api/latest/issue/TEST-1/comment/1 HTTP/1.1"));
+}
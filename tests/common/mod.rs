@@ -0,0 +1,71 @@
+// Shared helpers for integration tests
+#![allow(dead_code)]
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc;
+use std::thread;
+
+/// serves a single canned response on a local port, sending the raw
+/// request it received back over the returned channel
+pub fn stub(response: &str) -> (String, mpsc::Receiver<String>) {
+    stub_many(vec![response.to_owned()])
+}
+
+/// formats a raw http response with the given status line and body
+pub fn response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+/// serves canned responses, in order, to consecutive requests on a local port,
+/// sending each raw request it received back over the returned channel
+pub fn stub_many(responses: Vec<String>) -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let host = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut responses = responses.into_iter();
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            while let Some(request) = read_request(&mut stream) {
+                match responses.next() {
+                    Some(response) => {
+                        stream.write_all(response.as_bytes()).unwrap();
+                        tx.send(request).unwrap();
+                    }
+                    None => return,
+                }
+            }
+        }
+    });
+    (host, rx)
+}
+
+/// reads a single request, returning None when the connection is closed
+fn read_request(stream: &mut TcpStream) -> Option<String> {
+    let mut request = Vec::new();
+    let mut buf = [0; 4096];
+    loop {
+        let n = stream.read(&mut buf).ok()?;
+        if n == 0 {
+            return None;
+        }
+        request.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&request).to_string();
+        if let Some(end) = text.find("\r\n\r\n") {
+            let length = text[..end]
+                .lines()
+                .find(|line| line.to_lowercase().starts_with("content-length:"))
+                .and_then(|line| line[15..].trim().parse::<usize>().ok())
+                .unwrap_or(0);
+            if request.len() >= end + 4 + length {
+                return Some(text);
+            }
+        }
+    }
+}
@@ -1,7 +1,15 @@
 extern crate goji;
 extern crate serde_json;
 
+mod common;
+
+use common::{response, stub_many};
 use goji::issues::*;
+use goji::{Credentials, Jira};
+
+fn jira(host: String) -> Jira {
+    Jira::new(host, Credentials::Basic("user".into(), "pwd".into())).unwrap()
+}
 
 #[test]
 fn deserialise_issue_results() {
@@ -21,3 +29,100 @@ fn deserialise_issue_results() {
     assert_eq!(results.total, 0);
     assert_eq!(results.issues.len(), 0);
 }
+
+#[test]
+fn serialize_edit_issue() {
+    let edit = EditIssue::builder()
+        .summary("new summary")
+        .add_label("triaged")
+        .remove_label("new")
+        .notify_users(false)
+        .build();
+
+    assert_eq!(
+        serde_json::to_value(&edit).unwrap(),
+        serde_json::json!({
+            "fields": {"summary": "new summary"},
+            "update": {"labels": [{"add": "triaged"}, {"remove": "new"}]}
+        })
+    );
+    assert_eq!(edit.notify_users, Some(false));
+}
+
+#[test]
+fn serialize_empty_edit_issue() {
+    let edit = EditIssue::builder().build();
+
+    assert_eq!(serde_json::to_string(&edit).unwrap(), "{}");
+}
+
+#[test]
+fn serialize_minimal_create_issue() {
+    let issue = CreateIssue::builder("TEST", "10001", "summary").build();
+
+    assert_eq!(
+        serde_json::to_value(&issue).unwrap(),
+        serde_json::json!({"fields": {
+            "issuetype": {"id": "10001"},
+            "project": {"key": "TEST"},
+            "summary": "summary"
+        }})
+    );
+}
+
+#[test]
+fn serialize_create_issue_with_custom_fields() {
+    let issue = CreateIssue::builder("TEST", "10001", "summary")
+        .assignee("jdoe")
+        .component("backend")
+        .priority("3")
+        .field("customfield_10010", 5)
+        .build();
+
+    let value = serde_json::to_value(&issue).unwrap();
+    assert_eq!(value["fields"]["assignee"]["name"], "jdoe");
+    assert_eq!(value["fields"]["components"][0]["name"], "backend");
+    assert_eq!(value["fields"]["priority"]["id"], "3");
+    assert_eq!(value["fields"]["customfield_10010"], 5);
+    assert!(value["fields"].get("environment").is_none());
+}
+
+#[test]
+fn assign_issue_identifies_cloud_users_by_account_id() {
+    let (host, rx) = stub_many(vec![
+        response("204 No Content", ""),
+        response("204 No Content", ""),
+    ]);
+    let jira = jira(host);
+    jira.issues()
+        .assign(
+            "TEST-1",
+            Some(Account::AccountId("5b10a2844c20165700ede21g".into())),
+        )
+        .unwrap();
+    jira.issues().assign("TEST-1", None).unwrap();
+
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with("PUT /rest/api/latest/issue/TEST-1/assignee HTTP/1.1"));
+    assert!(request.ends_with("\r\n\r\n{\"accountId\":\"5b10a2844c20165700ede21g\"}"));
+    let request = rx.recv().unwrap();
+    assert!(request.ends_with("\r\n\r\n{\"accountId\":null,\"name\":null}"));
+}
+
+#[test]
+fn assign_issue_identifies_server_users_by_name() {
+    let (host, rx) = stub_many(vec![
+        response("204 No Content", ""),
+        response("204 No Content", ""),
+    ]);
+    let jira = jira(host);
+    jira.issues()
+        .assign("TEST-1", Some(Account::Name("jdoe".into())))
+        .unwrap();
+    jira.issues()
+        .assign("TEST-1", Some(Account::automatic()))
+        .unwrap();
+
+    assert!(rx.recv().unwrap().ends_with("\r\n\r\n{\"name\":\"jdoe\"}"));
+    assert!(rx.recv().unwrap().ends_with("\r\n\r\n{\"name\":\"-1\"}"));
+}
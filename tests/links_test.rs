@@ -0,0 +1,60 @@
+extern crate goji;
+
+mod common;
+
+use common::{response, stub, stub_many};
+use goji::*;
+
+fn jira(host: String) -> Jira {
+    Jira::new(host, Credentials::Basic("user".into(), "pwd".into())).unwrap()
+}
+
+#[test]
+fn create_link_names_type_and_issues() {
+    let (host, rx) = stub(&response("201 Created", ""));
+    let result =
+        jira(host)
+            .links()
+            .create(links::CreateIssueLink::new("Duplicate", "TEST-1", "TEST-2"));
+
+    assert!(result.is_ok());
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with("POST /rest/api/latest/issueLink HTTP/1.1"));
+    assert!(request.ends_with(
+        "\r\n\r\n{\"type\":{\"name\":\"Duplicate\"},\"inwardIssue\":{\"key\":\"TEST-1\"},\"outwardIssue\":{\"key\":\"TEST-2\"}}"
+    ));
+}
+
+#[test]
+fn links_and_link_types_are_fetched_and_deleted() {
+    let blocks = r#"{"id":"10000","name":"Blocks","inward":"is blocked by","outward":"blocks","self":"http://jira/rest/api/2/issueLinkType/10000"}"#;
+    let (host, rx) = stub_many(vec![
+        response("200 OK", &format!(r#"{{"issueLinkTypes":[{}]}}"#, blocks)),
+        response(
+            "200 OK",
+            &format!(
+                r#"{{"id":"1","self":"http://jira/rest/api/2/issueLink/1","type":{}}}"#,
+                blocks
+            ),
+        ),
+        response("204 No Content", ""),
+    ]);
+    let links = jira(host).links();
+
+    assert_eq!(links.types().unwrap()[0].outward, "blocks");
+    assert_eq!(links.get("1").unwrap().link_type.name, "Blocks");
+    links.delete("1").unwrap();
+
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("GET /rest/api/latest/issueLinkType HTTP/1.1"));
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("GET /rest/api/latest/issueLink/1 HTTP/1.1"));
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("DELETE /rest/api/latest/issueLink/1 HTTP/1.1"));
+}
@@ -0,0 +1,263 @@
+extern crate goji;
+extern crate serde_json;
+
+mod common;
+
+use common::{response, stub, stub_many};
+use goji::meta::*;
+use goji::*;
+use serde_json::json;
+use std::collections::BTreeMap;
+
+fn jira(host: String) -> Jira {
+    Jira::new(host, Credentials::Basic("user".into(), "pwd".into())).unwrap()
+}
+
+#[test]
+fn create_meta_expands_fields_of_projects() {
+    let (host, rx) = stub(&response("200 OK", r#"{"projects":[]}"#));
+    let meta = jira(host)
+        .issues()
+        .create_meta(vec!["TEST", "OTHER"])
+        .unwrap();
+
+    assert!(meta.projects.is_empty());
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with(
+        "GET /rest/api/latest/issue/createmeta?projectKeys=TEST%2COTHER&expand=projects.issuetypes.fields HTTP/1.1"
+    ));
+}
+
+#[test]
+fn create_and_edit_meta_are_paged_by_issue_type_and_field() {
+    let (host, rx) = stub_many(vec![
+        response(
+            "200 OK",
+            r#"{"startAt":0,"maxResults":2,"total":4,"values":[{"id":"10001","name":"Task","subtask":false}]}"#,
+        ),
+        response(
+            "200 OK",
+            r#"{"startAt":0,"maxResults":50,"total":1,"values":[{"fieldId":"parent","name":"Parent","required":true,"schema":{"type":"issuelink"}}]}"#,
+        ),
+        response(
+            "200 OK",
+            r#"{"fields":{"summary":{"name":"Summary","required":true,"schema":{"type":"string"},"operations":["set"]}}}"#,
+        ),
+    ]);
+    let issues = jira(host).issues();
+
+    let issuetypes = issues
+        .create_meta_issue_types("TEST", &SearchOptions::builder().max_results(2).build())
+        .unwrap();
+    assert_eq!(issuetypes.total, 4);
+    let fields = issues
+        .create_meta_fields("TEST", "10004", &SearchOptions::default())
+        .unwrap();
+    assert!(fields.values[0].required);
+    let meta = issues.edit_meta("TEST-1").unwrap();
+    assert!(meta.fields.contains_key("summary"));
+
+    assert!(rx.recv().unwrap().starts_with(
+        "GET /rest/api/latest/issue/createmeta/TEST/issuetypes?maxResults=2 HTTP/1.1"
+    ));
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("GET /rest/api/latest/issue/createmeta/TEST/issuetypes/10004 HTTP/1.1"));
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("GET /rest/api/latest/issue/TEST-1/editmeta HTTP/1.1"));
+}
+
+#[test]
+fn validate_create_with_paged_field_metadata() {
+    let results: FieldMetaResults = serde_json::from_value(json!({
+        "startAt": 0,
+        "maxResults": 50,
+        "total": 2,
+        "values": [
+            {
+                "fieldId": "summary",
+                "name": "Summary",
+                "required": true,
+                "schema": { "type": "string", "system": "summary" },
+                "operations": ["set"]
+            },
+            {
+                "fieldId": "components",
+                "name": "Component/s",
+                "required": true,
+                "schema": { "type": "array", "items": "component", "system": "components" },
+                "operations": ["add", "set", "remove"],
+                "allowedValues": [{ "id": "10000", "name": "backend" }]
+            }
+        ]
+    }))
+    .unwrap();
+    let mut fields = results
+        .values
+        .into_iter()
+        .map(|field| (field.field_id.clone().unwrap(), field))
+        .collect::<BTreeMap<_, _>>();
+    for (id, schema_type) in &[("project", "project"), ("issuetype", "issuetype")] {
+        fields.insert(
+            id.to_string(),
+            serde_json::from_value(json!({
+                "name": id,
+                "required": true,
+                "schema": { "type": schema_type }
+            }))
+            .unwrap(),
+        );
+    }
+
+    let issue = CreateIssue::builder("TEST", "10001", "summary")
+        .component("backend")
+        .build();
+    assert!(validate_create(&fields, &issue).is_empty());
+
+    let issue = CreateIssue::builder("TEST", "10001", "summary")
+        .component("frontend")
+        .build();
+    assert_eq!(
+        validate_create(&fields, &issue),
+        vec![Problem {
+            field: "components".into(),
+            kind: ProblemKind::NotAllowed(json!({ "name": "frontend" }))
+        }]
+    );
+}
+
+#[test]
+fn create_meta_validates_issues() {
+    let meta: CreateMeta = serde_json::from_value(json!({
+        "projects": [{
+            "id": "10000",
+            "key": "TEST",
+            "name": "Test project",
+            "issuetypes": [{
+                "id": "10002",
+                "name": "Bug",
+                "subtask": false,
+                "fields": {
+                    "project": { "name": "Project", "required": true, "schema": { "type": "project" } },
+                    "issuetype": { "name": "Issue Type", "required": true, "schema": { "type": "issuetype" } },
+                    "summary": { "name": "Summary", "required": true, "schema": { "type": "string" } },
+                    "priority": {
+                        "name": "Priority",
+                        "required": false,
+                        "schema": { "type": "priority" },
+                        "allowedValues": [{ "id": "1", "name": "Highest" }, { "id": "3", "name": "Medium" }]
+                    },
+                    "customfield_10010": {
+                        "name": "Severity",
+                        "required": true,
+                        "schema": { "type": "option", "custom": "com.atlassian.jira.plugin.system.customfieldtypes:select", "customId": 10010 },
+                        "allowedValues": [{ "id": "1", "value": "Critical" }, { "id": "2", "value": "Minor" }]
+                    }
+                }
+            }]
+        }]
+    }))
+    .unwrap();
+    assert_eq!(meta.issue_type("TEST", "10002").unwrap().name, "Bug");
+
+    let valid = CreateIssue::builder("TEST", "10002", "crash on start")
+        .priority("1")
+        .field("customfield_10010", json!({ "value": "Critical" }))
+        .build();
+    assert!(meta.validate(&valid).is_empty());
+
+    let invalid = CreateIssue::builder("TEST", "10002", "")
+        .priority("9")
+        .field("customfield_10020", json!(3))
+        .build();
+    let problems = meta.validate(&invalid);
+    assert_eq!(
+        problems,
+        vec![
+            Problem {
+                field: "customfield_10010".into(),
+                kind: ProblemKind::Missing
+            },
+            Problem {
+                field: "customfield_10020".into(),
+                kind: ProblemKind::Unknown
+            },
+            Problem {
+                field: "priority".into(),
+                kind: ProblemKind::NotAllowed(json!({ "id": "9" }))
+            },
+            Problem {
+                field: "summary".into(),
+                kind: ProblemKind::Missing
+            },
+        ]
+    );
+    assert_eq!(problems[3].to_string(), "summary is required");
+
+    let unknown = CreateIssue::builder("OTHER", "10002", "elsewhere").build();
+    assert_eq!(meta.validate(&unknown)[0].field, "project");
+    let unknown = CreateIssue::builder("TEST", "10001", "elsewhere").build();
+    assert_eq!(meta.validate(&unknown)[0].field, "issuetype");
+}
+
+#[test]
+fn edit_meta_validates_edits() {
+    let meta: EditMeta = serde_json::from_value(json!({
+        "fields": {
+            "summary": {
+                "name": "Summary",
+                "required": true,
+                "schema": { "type": "string", "system": "summary" },
+                "operations": ["set"]
+            },
+            "duedate": {
+                "name": "Due Date",
+                "required": false,
+                "schema": { "type": "date", "system": "duedate" },
+                "operations": ["set"]
+            },
+            "labels": {
+                "name": "Labels",
+                "required": false,
+                "schema": { "type": "array", "items": "string", "system": "labels" },
+                "operations": ["add", "set", "remove"]
+            },
+            "assignee": {
+                "name": "Assignee",
+                "required": false,
+                "schema": { "type": "user", "system": "assignee" },
+                "operations": ["set"]
+            }
+        }
+    }))
+    .unwrap();
+
+    let edit = EditIssue::builder()
+        .summary("")
+        .field("duedate", json!(20190101))
+        .add_label("triaged")
+        .operation("assignee", FieldOperation::Add(json!({ "name": "jdoe" })))
+        .build();
+    assert_eq!(
+        meta.validate(&edit),
+        vec![
+            Problem {
+                field: "duedate".into(),
+                kind: ProblemKind::WrongType {
+                    expected: "date".into()
+                }
+            },
+            Problem {
+                field: "summary".into(),
+                kind: ProblemKind::Missing
+            },
+            Problem {
+                field: "assignee".into(),
+                kind: ProblemKind::UnsupportedOperation("add".into())
+            },
+        ]
+    );
+}
@@ -0,0 +1,239 @@
+#![cfg(feature = "mock")]
+
+extern crate goji;
+extern crate serde_json;
+
+use goji::mock::MockServer;
+use goji::{
+    CreateIssue, Credentials, EditIssue, Error, Jira, SearchOptions, TransitionTriggerOptions,
+};
+use serde_json::{json, Value};
+
+fn setup() -> (MockServer, Jira) {
+    let server = MockServer::start().unwrap();
+    server.add_project("TEST", "Test project");
+    let jira = Jira::new(
+        server.url(),
+        Credentials::Basic("user".into(), "pwd".into()),
+    )
+    .unwrap();
+    (server, jira)
+}
+
+fn create(jira: &Jira, summary: &str) -> String {
+    jira.issues()
+        .create(CreateIssue::builder("TEST", "10001", summary).build())
+        .unwrap()
+        .key
+}
+
+#[test]
+fn create_transition_and_move_to_sprint() {
+    let (server, jira) = setup();
+    let board_id = server.add_board("TEST board", "TEST");
+    let sprint_id = server.add_sprint(board_id, "Sprint 1");
+
+    let key = create(&jira, "write tests");
+    assert_eq!(key, "TEST-1");
+    create(&jira, "ship it");
+
+    let issue = jira.issues().get(key.as_str()).unwrap();
+    assert_eq!(
+        issue.summary().transpose().unwrap(),
+        Some("write tests".to_owned())
+    );
+    assert_eq!(issue.status().unwrap().unwrap().name, "To Do");
+
+    let transitions = jira.transitions(key.as_str()).list().unwrap();
+    assert_eq!(transitions.len(), 1);
+    assert_eq!(transitions[0].to.name, "In Progress");
+    jira.transitions(key.as_str())
+        .trigger(TransitionTriggerOptions::new(transitions[0].id.clone()))
+        .unwrap();
+    let issue = jira.issues().get(key.as_str()).unwrap();
+    assert_eq!(issue.status().unwrap().unwrap().name, "In Progress");
+
+    jira.sprints()
+        .move_issues(sprint_id, vec![key.clone()])
+        .unwrap();
+    assert_eq!(server.sprint_of(&key), Some(sprint_id));
+
+    let results = jira
+        .search()
+        .list(
+            format!("sprint = {} AND status = \"In Progress\"", sprint_id),
+            &SearchOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(results.total, 1);
+    assert_eq!(results.issues[0].key, key);
+
+    let board = jira.boards().get(board_id.to_string()).unwrap();
+    let issues = jira
+        .issues()
+        .list(&board, &SearchOptions::default())
+        .unwrap();
+    assert_eq!(issues.total, 2);
+}
+
+#[test]
+fn search_supports_basic_jql() {
+    let (_server, jira) = setup();
+    for summary in &["alpha", "beta", "gamma"] {
+        create(&jira, summary);
+    }
+    let total = |jql: &str| {
+        jira.search()
+            .list(jql, &SearchOptions::default())
+            .unwrap()
+            .total
+    };
+    assert_eq!(total("project = TEST"), 3);
+    assert_eq!(total("project = OTHER"), 0);
+    assert_eq!(total("key in (TEST-1, TEST-3) ORDER BY key DESC"), 2);
+    assert_eq!(total("key not in (TEST-1)"), 2);
+    assert_eq!(total("summary ~ alp OR summary ~ gam"), 2);
+    assert_eq!(total("assignee is empty and status != Done"), 3);
+}
+
+#[test]
+fn search_paginates() {
+    let (_server, jira) = setup();
+    for n in 0..5 {
+        create(&jira, &format!("issue {}", n));
+    }
+    let options = SearchOptions::builder().max_results(2).build();
+    let keys = jira
+        .search()
+        .iter("project = TEST", &options)
+        .unwrap()
+        .map(|issue| issue.key)
+        .collect::<Vec<_>>();
+    assert_eq!(keys.len(), 5);
+}
+
+#[test]
+fn invalid_requests_fail() {
+    let (_server, jira) = setup();
+    let key = create(&jira, "write tests");
+
+    match jira.issues().get("TEST-99") {
+        Err(Error::NotFound) => (),
+        other => panic!("expected not found, got {:?}", other),
+    }
+    match jira.post::<Value, _>(
+        "api",
+        "/issue",
+        json!({"fields": {"project": {"key": "TEST"}, "issuetype": {"name": "Task"}}}),
+    ) {
+        Err(Error::Fault { errors, .. }) => assert!(errors.errors.contains_key("summary")),
+        other => panic!("expected fault, got {:?}", other),
+    }
+    // "Done" isn't reachable from "To Do"
+    match jira
+        .transitions(key.as_str())
+        .trigger(TransitionTriggerOptions::new("21"))
+    {
+        Err(Error::InvalidFields(errors)) => assert!(errors.errors.contains_key("transition")),
+        other => panic!("expected invalid fields, got {:?}", other),
+    }
+}
+
+#[test]
+fn update_sets_fields_and_applies_operations() {
+    let (server, jira) = setup();
+    let key = create(&jira, "write tests");
+
+    jira.issues()
+        .update(
+            key.as_str(),
+            EditIssue::builder()
+                .summary("write more tests")
+                .labels(vec!["new", "backend"])
+                .build(),
+        )
+        .unwrap();
+    jira.issues()
+        .update(
+            key.as_str(),
+            EditIssue::builder()
+                .add_label("triaged")
+                .remove_label("new")
+                .notify_users(false)
+                .build(),
+        )
+        .unwrap();
+
+    let issue = server.issue(&key).unwrap();
+    assert_eq!(issue["fields"]["summary"], "write more tests");
+    assert_eq!(issue["fields"]["labels"], json!(["backend", "triaged"]));
+
+    match jira
+        .issues()
+        .update(key.as_str(), EditIssue::builder().summary("").build())
+    {
+        Err(Error::InvalidFields(errors)) => assert!(errors.errors.contains_key("summary")),
+        other => panic!("expected invalid fields, got {:?}", other),
+    }
+}
+
+#[test]
+fn delete_requires_opting_in_to_deleting_subtasks() {
+    let (server, jira) = setup();
+    let parent = create(&jira, "parent");
+    let subtask = jira
+        .issues()
+        .create(
+            CreateIssue::builder("TEST", "10004", "child")
+                .field("parent", json!({ "key": parent }))
+                .build(),
+        )
+        .unwrap()
+        .key;
+
+    match jira.issues().delete(parent.as_str(), false) {
+        Err(Error::IssueHasSubtasks(errors)) => assert_eq!(errors.error_messages.len(), 1),
+        other => panic!("expected subtasks error, got {:?}", other),
+    }
+    assert!(server.issue(&parent).is_some());
+
+    jira.issues().delete(parent.as_str(), true).unwrap();
+    assert!(server.issue(&parent).is_none());
+    assert!(server.issue(&subtask).is_none());
+
+    match jira.issues().delete(parent.as_str(), false) {
+        Err(Error::NotFound) => (),
+        other => panic!("expected not found, got {:?}", other),
+    }
+}
+
+#[test]
+fn subtasks_reference_their_parent() {
+    let (_server, jira) = setup();
+    let parent = create(&jira, "story");
+    let subtask = jira
+        .issues()
+        .create(
+            CreateIssue::builder("TEST", "10004", "task")
+                .field("parent", json!({ "key": parent }))
+                .build(),
+        )
+        .unwrap()
+        .key;
+
+    let issue = jira.issues().get(subtask.as_str()).unwrap();
+    assert_eq!(issue.parent().unwrap().unwrap().key, parent);
+    assert_eq!(issue.status_category().unwrap().unwrap().key, "new");
+    let subtasks = jira
+        .issues()
+        .get(parent.as_str())
+        .unwrap()
+        .subtasks()
+        .unwrap()
+        .unwrap();
+    assert_eq!(subtasks[0].key, subtask);
+    assert_eq!(
+        subtasks[0].summary().transpose().unwrap(),
+        Some("task".to_owned())
+    );
+}
@@ -0,0 +1,182 @@
+#![cfg(feature = "oauth")]
+
+extern crate goji;
+extern crate openssl;
+extern crate percent_encoding;
+extern crate url;
+
+mod common;
+
+use common::{response, stub, stub_many};
+use goji::oauth::*;
+use goji::*;
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::rsa::Rsa;
+use openssl::sign::Verifier;
+use percent_encoding::percent_decode_str;
+use reqwest::Method;
+use std::time::Duration;
+use url::Url;
+
+fn private_key() -> (String, PKey<openssl::pkey::Public>) {
+    let rsa = Rsa::generate(2048).unwrap();
+    let public = PKey::public_key_from_pem(&rsa.public_key_to_pem().unwrap()).unwrap();
+    (
+        String::from_utf8(rsa.private_key_to_pem().unwrap()).unwrap(),
+        public,
+    )
+}
+
+/// extracts the decoded oauth parameters from a raw http request
+fn oauth_params(request: &str) -> Vec<(String, String)> {
+    let header = request
+        .lines()
+        .find(|line| line.to_lowercase().starts_with("authorization: oauth "))
+        .unwrap();
+    header["authorization: OAuth ".len()..]
+        .split(", ")
+        .map(|pair| {
+            let mut parts = pair.splitn(2, '=');
+            let k = parts.next().unwrap();
+            let v = parts.next().unwrap().trim_matches('"');
+            (decode(k), decode(v))
+        })
+        .collect()
+}
+
+fn decode(value: &str) -> String {
+    percent_decode_str(value)
+        .decode_utf8()
+        .unwrap()
+        .into_owned()
+}
+
+fn verify(public: &PKey<openssl::pkey::Public>, method: Method, url: &str, request: &str) {
+    let params = oauth_params(request);
+    let signature = params
+        .iter()
+        .find(|(k, _)| k == "oauth_signature")
+        .map(|(_, v)| base64::decode(v).unwrap())
+        .unwrap();
+    let unsigned = params
+        .iter()
+        .filter(|(k, _)| k != "oauth_signature")
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .collect::<Vec<_>>();
+    let base = signature_base_string(&method, &Url::parse(url).unwrap(), &unsigned);
+
+    let mut verifier = Verifier::new(MessageDigest::sha1(), public).unwrap();
+    verifier.update(base.as_bytes()).unwrap();
+    assert!(verifier.verify(&signature).unwrap());
+}
+
+#[test]
+fn signature_base_string_sorts_and_encodes_params() {
+    let url = Url::parse("http://jira.com/rest/api/latest/search?jql=a%20b&maxResults=1").unwrap();
+    let base = signature_base_string(
+        &Method::GET,
+        &url,
+        &[("oauth_nonce", "n"), ("oauth_consumer_key", "goji")],
+    );
+
+    assert_eq!(
+        base,
+        "GET&http%3A%2F%2Fjira.com%2Frest%2Fapi%2Flatest%2Fsearch&\
+         jql%3Da%2520b%26maxResults%3D1%26oauth_consumer_key%3Dgoji%26oauth_nonce%3Dn"
+    );
+}
+
+#[test]
+fn request_token_dance() {
+    let (key, public) = private_key();
+    let (host, rx) = stub(&response(
+        "200 OK",
+        "oauth_token=req-token&oauth_token_secret=s3",
+    ));
+    let oauth = OAuth::new(host.clone(), "goji", key).unwrap();
+    let token = oauth.request_token(None).unwrap();
+    let request = rx.recv().unwrap();
+
+    assert_eq!(token.token, "req-token");
+    assert_eq!(token.secret, "s3");
+    assert!(request.starts_with("POST /plugins/servlet/oauth/request-token HTTP/1.1"));
+    assert!(oauth_params(&request).contains(&("oauth_callback".into(), "oob".into())));
+    verify(
+        &public,
+        Method::POST,
+        &format!("{}/plugins/servlet/oauth/request-token", host),
+        &request,
+    );
+    assert_eq!(
+        oauth.authorize_url(&token),
+        format!(
+            "{}/plugins/servlet/oauth/authorize?oauth_token=req-token",
+            host
+        )
+    );
+}
+
+#[test]
+fn oauth_credentials_sign_requests() {
+    let (key, public) = private_key();
+    let (host, rx) = stub(&response("204 No Content", ""));
+    let jira = Jira::new(
+        host.clone(),
+        Credentials::OAuth(OAuthCredentials::new("goji", key, "access-token")),
+    )
+    .unwrap();
+    let _: EmptyResponse = jira.get("api", "/search?jql=project%3DTEST").unwrap();
+    let request = rx.recv().unwrap();
+
+    assert!(oauth_params(&request).contains(&("oauth_token".into(), "access-token".into())));
+    verify(
+        &public,
+        Method::GET,
+        &format!("{}/rest/api/latest/search?jql=project%3DTEST", host),
+        &request,
+    );
+}
+
+#[test]
+fn token_requests_are_retried_with_the_clients_policy() {
+    let (key, public) = private_key();
+    let (host, rx) = stub_many(vec![
+        response("503 Service Unavailable", "<html>down</html>"),
+        response("200 OK", "oauth_token=access&oauth_token_secret=s3"),
+    ]);
+    let jira = Jira::new(host.clone(), Credentials::Bearer("unused".into()))
+        .unwrap()
+        .with_retry_policy(
+            RetryPolicy::builder()
+                .initial_backoff(Duration::from_millis(1))
+                .methods(vec![Method::POST])
+                .build(),
+        );
+    let oauth = jira.oauth("goji", key);
+    let token = Token {
+        token: "req-token".into(),
+        secret: "s3".into(),
+    };
+    let access = oauth.access_token(&token, "1234").unwrap();
+    let first = rx.recv().unwrap();
+    let request = rx.recv().unwrap();
+    let nonce = |request: &str| {
+        oauth_params(request)
+            .into_iter()
+            .find(|(k, _)| k == "oauth_nonce")
+            .map(|(_, v)| v)
+            .unwrap()
+    };
+
+    assert_eq!(access.token, "access");
+    assert_ne!(nonce(&first), nonce(&request));
+    assert!(request.starts_with("POST /plugins/servlet/oauth/access-token HTTP/1.1"));
+    assert!(oauth_params(&request).contains(&("oauth_verifier".into(), "1234".into())));
+    verify(
+        &public,
+        Method::POST,
+        &format!("{}/plugins/servlet/oauth/access-token", host),
+        &request,
+    );
+}
@@ -0,0 +1,65 @@
+extern crate goji;
+
+mod common;
+
+use common::{response, stub, stub_many};
+use goji::*;
+
+fn jira(host: String) -> Jira {
+    Jira::new(host, Credentials::Basic("user".into(), "pwd".into())).unwrap()
+}
+
+#[test]
+fn find_remote_link_encodes_global_id() {
+    let (host, rx) = stub(&response("404 Not Found", ""));
+    let result = jira(host)
+        .remote_links("TEST-1")
+        .find("system=http://example.com&id=1");
+
+    assert!(result.is_err());
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with(
+        "GET /rest/api/latest/issue/TEST-1/remotelink?globalId=system%3Dhttp%3A%2F%2Fexample.com%26id%3D1 HTTP/1.1"
+    ));
+}
+
+#[test]
+fn remote_links_are_upserted_listed_and_deleted_by_global_id() {
+    let (host, rx) = stub_many(vec![
+        response(
+            "200 OK",
+            r#"{"id":10000,"self":"http://jira/rest/api/2/issue/TEST-1/remotelink/10000"}"#,
+        ),
+        response(
+            "200 OK",
+            r#"[{"id":10000,"self":"http://jira/rest/api/2/issue/TEST-1/remotelink/10000","globalId":"ci=builds&id=1","object":{"url":"https://ci.example.com/builds/1","title":"build passed","status":{"resolved":true}}}]"#,
+        ),
+        response("204 No Content", ""),
+    ]);
+    let remote_links = jira(host).remote_links("TEST-1");
+
+    let created = remote_links
+        .create(
+            remote_links::CreateRemoteLink::new("https://ci.example.com/builds/1", "build passed")
+                .global_id("ci=builds&id=1")
+                .resolved(true),
+        )
+        .unwrap();
+    assert_eq!(created.id, 10000);
+    let links = remote_links.list().unwrap();
+    assert_eq!(links[0].global_id, Some("ci=builds&id=1".to_owned()));
+    remote_links.delete_global("ci=builds&id=1").unwrap();
+
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with("POST /rest/api/latest/issue/TEST-1/remotelink HTTP/1.1"));
+    assert!(request.ends_with(
+        r#"{"globalId":"ci=builds&id=1","object":{"url":"https://ci.example.com/builds/1","title":"build passed","status":{"resolved":true}}}"#
+    ));
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("GET /rest/api/latest/issue/TEST-1/remotelink HTTP/1.1"));
+    assert!(rx.recv().unwrap().starts_with(
+        "DELETE /rest/api/latest/issue/TEST-1/remotelink?globalId=ci%3Dbuilds%26id%3D1 HTTP/1.1"
+    ));
+}
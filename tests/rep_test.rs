@@ -24,5 +24,121 @@ fn issue_getters() {
     let expected_resolution_date = Some("2018-07-11T16:56:12.000+0000".to_owned());
 
     assert_eq!(issue.permalink(&jira), expected_permalink);
-    assert_eq!(issue.resolution_date(), expected_resolution_date);
+    assert_eq!(
+        issue.resolution_date().transpose().unwrap(),
+        expected_resolution_date
+    );
+}
+
+#[test]
+fn cloud_users_are_identified_by_account_id() {
+    let issue: Issue = serde_json::from_value(serde_json::json!({
+        "self": "https://example.atlassian.net/rest/api/2/issue/10000",
+        "id": "10000",
+        "key": "TEST-1",
+        "fields": {
+            "assignee": {
+                "self": "https://example.atlassian.net/rest/api/2/user?accountId=5b10a2844c20165700ede21g",
+                "accountId": "5b10a2844c20165700ede21g",
+                "accountType": "atlassian",
+                "avatarUrls": {},
+                "displayName": "Mia Krystof",
+                "active": true,
+                "timeZone": "Australia/Sydney"
+            },
+            "reporter": {
+                "self": "https://jira.com/rest/api/2/user?username=jdoe",
+                "name": "jdoe",
+                "key": "jdoe",
+                "emailAddress": "jdoe@example.com",
+                "avatarUrls": {},
+                "displayName": "John Doe",
+                "active": true
+            }
+        }
+    }))
+    .unwrap();
+
+    let assignee = issue.assignee().unwrap().unwrap();
+    assert_eq!(assignee.name, None);
+    assert_eq!(assignee.email_address, None);
+    assert_eq!(assignee.account_type, Some("atlassian".to_owned()));
+    assert_eq!(
+        assignee.account(),
+        Some(Account::AccountId("5b10a2844c20165700ede21g".to_owned()))
+    );
+    assert_eq!(
+        issue.reporter().unwrap().unwrap().account(),
+        Some(Account::Name("jdoe".to_owned()))
+    );
+}
+
+#[test]
+fn field_getters_distinguish_missing_from_malformed() {
+    let issue: Issue = serde_json::from_value(serde_json::json!({
+        "self": "https://jira.com/rest/api/2/issue/10001",
+        "id": "10001",
+        "key": "TEST-2",
+        "fields": {
+            "duedate": "2019-01-31",
+            "environment": null,
+            "components": [{
+                "self": "https://jira.com/rest/api/2/component/10000",
+                "id": "10000",
+                "name": "backend"
+            }],
+            "versions": "1.0",
+            "parent": {
+                "self": "https://jira.com/rest/api/2/issue/10000",
+                "id": "10000",
+                "key": "TEST-1",
+                "fields": { "summary": "parent" }
+            },
+            "security": {
+                "self": "https://jira.com/rest/api/2/securitylevel/10100",
+                "id": "10100",
+                "name": "Internal"
+            },
+            "aggregateprogress": { "progress": 3600, "total": 7200, "percent": 50 },
+            "votes": { "self": "https://jira.com/rest/api/2/issue/TEST-2/votes", "votes": 2, "hasVoted": false },
+            "watches": { "self": "https://jira.com/rest/api/2/issue/TEST-2/watchers", "watchCount": 1, "isWatching": true },
+            "status": {
+                "self": "https://jira.com/rest/api/2/status/10000",
+                "description": "",
+                "iconUrl": "https://jira.com/images/icons/statuses/open.png",
+                "id": "10000",
+                "name": "Backlog",
+                "statusCategory": {
+                    "self": "https://jira.com/rest/api/2/statuscategory/2",
+                    "id": 2,
+                    "key": "new",
+                    "colorName": "blue-gray",
+                    "name": "To Do"
+                }
+            }
+        }
+    }))
+    .unwrap();
+
+    assert_eq!(
+        issue.due_date().transpose().unwrap(),
+        Some("2019-01-31".to_owned())
+    );
+    assert!(issue.environment().is_none());
+    assert!(issue.subtasks().is_none());
+    assert!(matches!(issue.versions(), Some(Err(Error::Serde(_)))));
+    assert_eq!(issue.components().unwrap().unwrap()[0].name, "backend");
+    assert_eq!(issue.parent().unwrap().unwrap().key, "TEST-1");
+    assert_eq!(issue.security_level().unwrap().unwrap().name, "Internal");
+    assert_eq!(
+        issue.aggregate_progress().unwrap().unwrap(),
+        Progress {
+            progress: 3600,
+            total: 7200,
+            percent: Some(50)
+        }
+    );
+    assert_eq!(issue.votes().unwrap().unwrap().votes, 2);
+    assert!(issue.watches().unwrap().unwrap().is_watching);
+    assert_eq!(issue.status_category().unwrap().unwrap().key, "new");
 }
@@ -0,0 +1,47 @@
+extern crate goji;
+extern crate serde_json;
+
+use goji::transport::{HeaderMap, StatusCode};
+use goji::*;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+/// an in-memory transport which answers every request with the same body
+#[derive(Debug, Default)]
+struct Fake {
+    requests: Arc<Mutex<Vec<HttpRequest>>>,
+}
+
+impl Transport for Fake {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        self.requests.lock().unwrap().push(request);
+        Ok(HttpResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Box::new(Cursor::new(
+                r#"{"transitions": [{"id": "11", "name": "Start", "to": {"id": "3", "name": "In Progress"}}]}"#,
+            )),
+        })
+    }
+}
+
+#[test]
+fn requests_are_sent_through_transport() {
+    let fake = Fake::default();
+    let requests = fake.requests.clone();
+    let jira =
+        Jira::from_transport("http://jira.com", Credentials::Bearer("token".into()), fake).unwrap();
+
+    let options = jira.transitions("TEST-1").list().unwrap();
+    let requests = requests.lock().unwrap();
+
+    assert_eq!(options[0].name, "Start");
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(
+        requests[0].url,
+        "http://jira.com/rest/api/latest/issue/TEST-1/transitions?expand=transitions.fields"
+    );
+    assert_eq!(requests[0].headers["authorization"], "Bearer token");
+    assert!(requests[0].body.is_none());
+}
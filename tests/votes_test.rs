@@ -0,0 +1,47 @@
+extern crate goji;
+
+mod common;
+
+use common::{response, stub, stub_many};
+use goji::*;
+
+fn jira(host: String) -> Jira {
+    Jira::new(host, Credentials::Basic("user".into(), "pwd".into())).unwrap()
+}
+
+#[test]
+fn vote_sends_no_body() {
+    let (host, rx) = stub(&response("204 No Content", ""));
+    let result = jira(host).votes("TEST-1").vote();
+
+    assert!(result.is_ok());
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with("POST /rest/api/latest/issue/TEST-1/votes HTTP/1.1"));
+    assert!(request.ends_with("\r\n\r\n"));
+}
+
+#[test]
+fn votes_are_listed_and_withdrawn() {
+    let (host, rx) = stub_many(vec![
+        response(
+            "200 OK",
+            r#"{"votes":1,"hasVoted":true,"voters":[{"active":true,"avatarUrls":{},"displayName":"User","emailAddress":"user@example.com","name":"user","self":"http://jira/rest/api/2/user?username=user"}]}"#,
+        ),
+        response("204 No Content", ""),
+    ]);
+    let votes = jira(host).votes("TEST-1");
+
+    let results = votes.list().unwrap();
+    assert!(results.has_voted);
+    assert_eq!(results.voters[0].name.as_deref(), Some("user"));
+    votes.unvote().unwrap();
+
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("GET /rest/api/latest/issue/TEST-1/votes HTTP/1.1"));
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("DELETE /rest/api/latest/issue/TEST-1/votes HTTP/1.1"));
+}
@@ -0,0 +1,51 @@
+extern crate goji;
+
+mod common;
+
+use common::{response, stub};
+use goji::*;
+
+fn jira(host: String) -> Jira {
+    Jira::new(host, Credentials::Basic("user".into(), "pwd".into())).unwrap()
+}
+
+#[test]
+fn add_watcher_sends_username_as_json_string() {
+    let (host, rx) = stub(&response("204 No Content", ""));
+    let result = jira(host).watchers("TEST-1").add("jdoe");
+
+    assert!(result.is_ok());
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with("POST /rest/api/latest/issue/TEST-1/watchers HTTP/1.1"));
+    assert!(request.ends_with("\r\n\r\n\"jdoe\""));
+}
+
+#[test]
+fn watchers_are_listed() {
+    let (host, rx) = stub(&response(
+        "200 OK",
+        r#"{"isWatching":true,"watchCount":1,"watchers":[{"active":true,"avatarUrls":{},"displayName":"User","emailAddress":"user@example.com","name":"user","self":"http://jira/rest/api/2/user?username=user"}]}"#,
+    ));
+    let watchers = jira(host).watchers("TEST-1").list().unwrap();
+
+    assert!(watchers.is_watching);
+    assert_eq!(watchers.watchers[0].account(), Some(Account::from("user")));
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("GET /rest/api/latest/issue/TEST-1/watchers HTTP/1.1"));
+}
+
+#[test]
+fn remove_watcher_identifies_cloud_users_by_account_id() {
+    let (host, rx) = stub(&response("204 No Content", ""));
+    let result = jira(host)
+        .watchers("TEST-1")
+        .remove(Account::AccountId("5b10a2844c20165700ede21g".into()));
+
+    assert!(result.is_ok());
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with(
+        "DELETE /rest/api/latest/issue/TEST-1/watchers?accountId=5b10a2844c20165700ede21g HTTP/1.1"
+    ));
+}
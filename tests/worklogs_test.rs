@@ -0,0 +1,98 @@
+extern crate goji;
+
+mod common;
+
+use common::{response, stub, stub_many};
+use goji::*;
+
+fn jira(host: String) -> Jira {
+    Jira::new(host, Credentials::Basic("user".into(), "pwd".into())).unwrap()
+}
+
+#[test]
+fn delete_worklog_sends_manual_adjustment() {
+    let (host, rx) = stub(&response("204 No Content", ""));
+    let result = jira(host).worklogs().delete(
+        "TEST-1",
+        "100",
+        worklogs::AdjustEstimate::Manual("1h 30m".into()),
+    );
+
+    assert!(result.is_ok());
+    assert!(rx.recv().unwrap().starts_with(
+        "DELETE /rest/api/latest/issue/TEST-1/worklog/100?adjustEstimate=manual&increaseBy=1h+30m HTTP/1.1"
+    ));
+}
+
+#[test]
+fn add_worklog_adjusts_estimate_automatically() {
+    let (host, rx) = stub(&response(
+        "201 Created",
+        r#"{"id":"100","self":"http://jira/rest/api/2/issue/1/worklog/100","created":"2020-01-01T00:00:00.000+0000","updated":"2020-01-01T00:00:00.000+0000","started":"2020-01-01T00:00:00.000+0000","timeSpent":"4h","timeSpentSeconds":14400}"#,
+    ));
+    let worklog = jira(host)
+        .worklogs()
+        .add(
+            "TEST-1",
+            worklogs::AddWorklog::new("4h").comment("investigating"),
+            worklogs::AdjustEstimate::Auto,
+        )
+        .unwrap();
+
+    assert_eq!(worklog.time_spent_seconds, 4 * 3600);
+    let request = rx.recv().unwrap();
+    assert!(request
+        .starts_with("POST /rest/api/latest/issue/TEST-1/worklog?adjustEstimate=auto HTTP/1.1"));
+    assert!(request.ends_with(r#"{"comment":"investigating","timeSpent":"4h"}"#));
+}
+
+#[test]
+fn worklogs_updated_since_follows_changes_and_lists_them_by_id() {
+    let (host, rx) = stub_many(vec![
+        response(
+            "200 OK",
+            r#"{"values":[{"worklogId":100,"updatedTime":1000}],"since":0,"until":1000,"lastPage":false}"#,
+        ),
+        response(
+            "200 OK",
+            r#"{"values":[{"worklogId":101,"updatedTime":2000}],"since":1000,"until":2000,"lastPage":true}"#,
+        ),
+        response(
+            "200 OK",
+            r#"[{"id":"100","self":"http://jira/rest/api/2/issue/1/worklog/100","created":"2020-01-01T00:00:00.000+0000","updated":"2020-01-01T00:00:00.000+0000","started":"2020-01-01T00:00:00.000+0000","timeSpentSeconds":3600}]"#,
+        ),
+    ]);
+    let worklogs = jira(host).worklogs().updated_since(0).unwrap();
+
+    assert_eq!(worklogs[0].id, "100");
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("GET /rest/api/latest/worklog/updated?since=0 HTTP/1.1"));
+    assert!(rx
+        .recv()
+        .unwrap()
+        .starts_with("GET /rest/api/latest/worklog/updated?since=1000 HTTP/1.1"));
+    let request = rx.recv().unwrap();
+    assert!(request.starts_with("POST /rest/api/latest/worklog/list HTTP/1.1"));
+    assert!(request.ends_with(r#"{"ids":[100,101]}"#));
+}
+
+#[test]
+fn update_worklog_sends_new_estimate() {
+    let (host, rx) = stub(&response(
+        "200 OK",
+        r#"{"id":"100","self":"http://jira/rest/api/2/issue/1/worklog/100","created":"2020-01-01T00:00:00.000+0000","updated":"2020-01-01T00:00:00.000+0000","started":"2020-01-01T00:00:00.000+0000","timeSpent":"2h","timeSpentSeconds":7200}"#,
+    ));
+    let result = jira(host).worklogs().update(
+        "TEST-1",
+        "100",
+        worklogs::AddWorklog::new("2h"),
+        worklogs::UpdateEstimate::New("1d".into()),
+    );
+
+    assert!(result.is_ok(), "{:?}", result);
+    assert!(rx.recv().unwrap().starts_with(
+        "PUT /rest/api/latest/issue/TEST-1/worklog/100?adjustEstimate=new&newEstimate=1d HTTP/1.1"
+    ));
+}